@@ -0,0 +1,189 @@
+//! Privileged helper for Linux, invoked by Porter through `pkexec`.
+//!
+//! It performs a single narrowly scoped operation: sending one of a few signals to one PID,
+//! after re-verifying that the PID still refers to the process Porter showed the user
+//! (same start time) and that the process actually holds the given port. Root-owned
+//! processes and those in systemd's system slice are always refused. The process is
+//! pinned with a pidfd before the checks, so a PID reused in between is never signalled.
+//!
+//! Usage: porter-helper signal <pid> <start-time> <TERM|KILL|STOP|CONT> <port>
+//!
+//! Exit codes: 0 success, 2 bad usage, 3 verification failed, 4 signal failed.
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    std::process::exit(run(&args));
+}
+
+#[cfg(target_os = "linux")]
+fn run(args: &[String]) -> i32 {
+    use std::os::fd::AsRawFd;
+
+    let (pid, start_time, signal, port) = match parse_args(args) {
+        Some(parsed) => parsed,
+        None => {
+            eprintln!("usage: porter-helper signal <pid> <start-time> <TERM|KILL|STOP|CONT> <port>");
+            return 2;
+        }
+    };
+
+    // Refers to this exact process from here on, even if the PID is reused
+    let pidfd = match open_pidfd(pid) {
+        Ok(pidfd) => pidfd,
+        Err(e) if e.raw_os_error() == Some(libc::ESRCH) => {
+            eprintln!("porter-helper: refusing to signal PID {}: no such process", pid);
+            return 3;
+        }
+        Err(e) => {
+            eprintln!("porter-helper: cannot pin PID {}: {}", pid, e);
+            return 4;
+        }
+    };
+
+    if let Err(reason) = verify(pid, start_time, port) {
+        eprintln!("porter-helper: refusing to signal PID {}: {}", pid, reason);
+        return 3;
+    }
+
+    let sent = unsafe {
+        libc::syscall(
+            libc::SYS_pidfd_send_signal,
+            pidfd.as_raw_fd(),
+            signal,
+            std::ptr::null::<libc::siginfo_t>(),
+            0,
+        )
+    };
+    if sent != 0 {
+        eprintln!(
+            "porter-helper: failed to signal PID {}: {}",
+            pid,
+            std::io::Error::last_os_error()
+        );
+        return 4;
+    }
+
+    0
+}
+
+/// `pidfd_open(2)`; needs Linux 5.3
+#[cfg(target_os = "linux")]
+fn open_pidfd(pid: u32) -> std::io::Result<std::os::fd::OwnedFd> {
+    use std::os::fd::FromRawFd;
+
+    let fd = unsafe { libc::syscall(libc::SYS_pidfd_open, pid as libc::pid_t, 0) };
+    if fd < 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(unsafe { std::os::fd::OwnedFd::from_raw_fd(fd as libc::c_int) })
+}
+
+#[cfg(not(target_os = "linux"))]
+fn run(_args: &[String]) -> i32 {
+    eprintln!("porter-helper is only supported on Linux");
+    2
+}
+
+#[cfg(target_os = "linux")]
+fn parse_args(args: &[String]) -> Option<(u32, u64, i32, u16)> {
+    if args.len() != 5 || args[0] != "signal" {
+        return None;
+    }
+
+    let pid = args[1].parse().ok()?;
+    let start_time = args[2].parse().ok()?;
+    let signal = match args[3].as_str() {
+        "TERM" => libc::SIGTERM,
+        "KILL" => libc::SIGKILL,
+        "STOP" => libc::SIGSTOP,
+        "CONT" => libc::SIGCONT,
+        _ => return None,
+    };
+    let port = args[4].parse().ok()?;
+
+    Some((pid, start_time, signal, port))
+}
+
+#[cfg(target_os = "linux")]
+fn verify(pid: u32, start_time: u64, port: u16) -> Result<(), String> {
+    use std::fs;
+
+    if pid <= 1 || pid == std::process::id() {
+        return Err("protected PID".to_string());
+    }
+
+    // Kernel threads have no command line and must never be signalled
+    let cmdline = fs::read(format!("/proc/{}/cmdline", pid)).map_err(|_| "no such process".to_string())?;
+    if cmdline.is_empty() {
+        return Err("kernel thread".to_string());
+    }
+
+    let stat = fs::read_to_string(format!("/proc/{}/stat", pid)).map_err(|_| "no such process".to_string())?;
+    // Fields after the command name; starttime is field 22 overall
+    let actual = stat
+        .rsplit_once(')')
+        .and_then(|(_, rest)| rest.split_whitespace().nth(19))
+        .and_then(|field| field.parse::<u64>().ok())
+        .ok_or_else(|| "unreadable process stat".to_string())?;
+    if actual != start_time {
+        return Err("PID was reused by another process".to_string());
+    }
+
+    // Root's processes and system services are never the target of a port cleanup
+    let status = fs::read_to_string(format!("/proc/{}/status", pid)).map_err(|_| "no such process".to_string())?;
+    let uids: Vec<&str> = status
+        .lines()
+        .find_map(|line| line.strip_prefix("Uid:"))
+        .map(|uids| uids.split_whitespace().collect())
+        .unwrap_or_default();
+    if uids.is_empty() || uids.contains(&"0") {
+        return Err("process is owned by root".to_string());
+    }
+
+    let cgroups = fs::read_to_string(format!("/proc/{}/cgroup", pid)).map_err(|_| "no such process".to_string())?;
+    let system = cgroups.lines().filter_map(|line| line.splitn(3, ':').nth(2)).any(|path| {
+        path == "/init.scope" || path == "/system.slice" || path.starts_with("/system.slice/")
+    });
+    if system {
+        return Err("process belongs to a system service".to_string());
+    }
+
+    if !holds_port(pid, port) {
+        return Err(format!("process does not hold port {}", port));
+    }
+
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn holds_port(pid: u32, port: u16) -> bool {
+    use std::fs;
+
+    let mut inodes = Vec::new();
+    for table in ["tcp", "tcp6", "udp", "udp6"] {
+        let Ok(content) = fs::read_to_string(format!("/proc/net/{}", table)) else {
+            continue;
+        };
+        for line in content.lines().skip(1) {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            if parts.len() < 10 {
+                continue;
+            }
+            let local_port = parts[1]
+                .rsplit_once(':')
+                .and_then(|(_, p)| u16::from_str_radix(p, 16).ok());
+            if local_port == Some(port) {
+                inodes.push(format!("socket:[{}]", parts[9]));
+            }
+        }
+    }
+
+    let Ok(fds) = fs::read_dir(format!("/proc/{}/fd", pid)) else {
+        return false;
+    };
+    fds.flatten().any(|fd| {
+        fs::read_link(fd.path())
+            .map(|link| inodes.iter().any(|inode| link.to_string_lossy() == *inode))
+            .unwrap_or(false)
+    })
+}
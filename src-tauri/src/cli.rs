@@ -0,0 +1,927 @@
+use crate::models::{
+    LaunchSpec, PauseMethod, PausedProcess, PortRange, PortReservations, Protocol, RestartOptions,
+};
+use crate::services::ephemeral::EphemeralRange;
+use crate::services::{
+    PortMonitor, ProcFs, ProcessManager, diagnostics, exposure, procfs, reservations, service_graph,
+    system_info,
+};
+use anyhow::{Result, anyhow};
+
+const USAGE: &str = "Usage: porter <command> [options]
+
+Commands:
+  restart <port> [-- <program> [args...]]
+                    Kill the process owning <port> and relaunch it detached,
+                    optionally with the command line given after `--`
+      --cwd <dir>         Replace the captured working directory
+      --env KEY=VALUE     Set an environment variable (repeatable)
+      --unset KEY         Remove an environment variable (repeatable)
+      --timeout <ms>      How long to wait for the port to be released
+      --dry-run           Only print what would be relaunched
+  process <pid>     List every socket <pid> has open
+      --tree              Include its descendants
+  ancestry <pid>    Show the processes that started <pid> and where it was launched from
+  impact <pid>      Show what killing <pid> would disrupt
+  pause <pid>       Pause a process without killing it
+      --cgroup            Freeze the process's whole cgroup (Linux only)
+  resume <pid>      Resume a paused process
+  paused            List processes paused by Porter
+  diagnose <port>   Explain why <port> can't be bound and how to free it
+      --json              Print the full diagnosis as JSON
+  connections <port>  Show each socket on <port>, with queues, timers and RTT for TCP
+  backlog           Show how full each listener's accept queue is
+      --watch <secs>      Keep sampling and report new listen overflows
+  leaks             Show per-process socket states and descriptor usage
+      --watch <secs>      Keep sampling and report processes trending toward a leak
+  interfaces        List interfaces and the listeners reachable on each
+  ephemeral         Show ports inside the ephemeral range and client sockets
+                    holding service ports
+      --service <port>    Also treat <port> as a service port (repeatable)
+  graph             Show which local processes connect to which local listeners
+      --dot               Print the graph as Graphviz DOT
+      --mermaid           Print the graph as a Mermaid flowchart
+      --json              Print the graph as JSON
+  exposure          Classify listeners by who can reach them
+      --json              Print the full report as JSON
+      --markdown          Print the report as a Markdown document
+  sockets           List listening Unix domain sockets and their owners
+      --all               Include connected and unnamed sockets
+      --stale             Only show sockets whose file was deleted
+  other-sockets     List raw, ICMP, packet and SCTP sockets and their owners
+  kill-socket <path>  Kill the process listening on a Unix socket path
+  kill-privileged <pid> <port>
+                    Kill another user's process holding <port> through the
+                    pkexec helper (Linux)
+  sysinfo           Show OS details and the kernel's networking limits
+  reserved          Show ports kept out of the ephemeral range
+  reserve <ports>   Reserve ports, e.g. `reserve 8080 9000-9010` (Linux, root)
+  unreserve <ports> Remove ports from the reserved list (Linux, root)
+  diagnostics       Show the scan backend, privileges and what they hide
+  audit             Show the log of kill attempts, newest first
+      --limit <n>         Only show the last <n> entries (default 50)
+  help              Show this message";
+
+/// Run a CLI subcommand if one was given, returning the exit code.
+/// Returns `None` when the arguments are not a CLI invocation so the GUI starts instead.
+pub fn run(args: &[String]) -> Option<i32> {
+    let (command, rest) = args.split_first()?;
+
+    let result = match command.as_str() {
+        "restart" => restart(rest),
+        "process" => process_sockets(rest),
+        "ancestry" => ancestry(rest),
+        "impact" => impact(rest),
+        "pause" => pause(rest),
+        "resume" => resume(rest),
+        "paused" => paused(),
+        "diagnose" => diagnose(rest),
+        "connections" => connections(rest),
+        "backlog" => backlog(rest),
+        "leaks" => leaks(rest),
+        "interfaces" => print_interfaces(),
+        "ephemeral" => ephemeral(rest),
+        "graph" => print_service_graph(rest),
+        "exposure" => print_exposure(rest),
+        "sockets" => sockets(rest),
+        "other-sockets" => other_sockets(),
+        "kill-socket" => kill_socket(rest),
+        "kill-privileged" => kill_privileged(rest),
+        "sysinfo" => print_system_info(),
+        "reserved" => print_reservations(reservations::get_reservations(&ProcFs::new())),
+        "reserve" => reserve(rest),
+        "unreserve" => unreserve(rest),
+        "diagnostics" => print_diagnostics(),
+        "audit" => audit(rest),
+        "help" | "--help" | "-h" => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        _ => return None,
+    };
+
+    match result {
+        Ok(()) => Some(0),
+        Err(e) => {
+            eprintln!("porter: {}", e);
+            Some(1)
+        }
+    }
+}
+
+fn restart(args: &[String]) -> Result<()> {
+    let mut args = args.iter();
+    let port = parse_port(args.next())?;
+    let mut options = RestartOptions::default();
+    let mut dry_run = false;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            // Everything after `--` is the replacement argv, quoted by the shell as usual
+            "--" => {
+                options.command = Some(args.by_ref().cloned().collect());
+            }
+            "--cwd" => options.working_dir = Some(value(&mut args, arg)?.clone()),
+            "--env" => {
+                let pair = value(&mut args, arg)?;
+                let (key, val) = pair
+                    .split_once('=')
+                    .ok_or_else(|| anyhow!("--env expects KEY=VALUE, got '{}'", pair))?;
+                options.env.insert(key.to_string(), val.to_string());
+            }
+            "--unset" => options.unset_env.push(value(&mut args, arg)?.clone()),
+            "--timeout" => {
+                let ms = value(&mut args, arg)?;
+                options.timeout_ms = Some(ms.parse().map_err(|_| anyhow!("Invalid timeout: {}", ms))?);
+            }
+            "--dry-run" => dry_run = true,
+            other => return Err(anyhow!("Unknown option for restart: {}", other)),
+        }
+    }
+
+    let mut manager = ProcessManager::new();
+
+    if dry_run {
+        let spec = manager.launch_spec_for_port(port)?;
+        print_launch_spec(&spec);
+        return Ok(());
+    }
+
+    let result = manager.restart_port_owner(port, options)?;
+    println!(
+        "Restarted port {}: PID {} -> PID {}",
+        result.port, result.old_pid, result.new_pid
+    );
+    print_launch_spec(&result.spec);
+
+    Ok(())
+}
+
+fn process_sockets(args: &[String]) -> Result<()> {
+    let pid = parse_pid(args.first())?;
+    let tree = match args.get(1).map(String::as_str) {
+        None => false,
+        Some("--tree") => true,
+        Some(other) => return Err(anyhow!("Unknown option for process: {}", other)),
+    };
+
+    let sockets = PortMonitor::new().get_process_sockets(pid, tree)?;
+    for entry in std::iter::once(&sockets).chain(&sockets.descendants) {
+        let process = &entry.process;
+        println!("{} (PID {})  {}", process.name, process.pid, process.command);
+
+        for conn in entry.listeners.iter().chain(&entry.connections) {
+            let remote = if conn.remote_port == 0 {
+                String::new()
+            } else {
+                format!(" -> {}:{}", conn.remote_address, conn.remote_port)
+            };
+            println!("  {:<12} {}:{}{}", conn.state, conn.local_address, conn.local_port, remote);
+        }
+        for socket in &entry.unix_sockets {
+            println!(
+                "  {:<12} unix {:?} {}{}",
+                socket.state,
+                socket.socket_type,
+                socket.path.as_deref().unwrap_or("(unnamed)"),
+                if socket.stale { "  (deleted)" } else { "" }
+            );
+        }
+        if entry.listeners.is_empty() && entry.connections.is_empty() && entry.unix_sockets.is_empty() {
+            println!("  no sockets");
+        }
+    }
+
+    Ok(())
+}
+
+fn ancestry(args: &[String]) -> Result<()> {
+    let pid = parse_pid(args.first())?;
+    let ancestry = PortMonitor::new().get_process_ancestry(pid)?;
+
+    let process = &ancestry.process;
+    println!("{:>7}  {}  {}", process.pid, process.name, process.command);
+    if let Some(cwd) = &process.working_dir {
+        println!("           in {}", cwd);
+    }
+    for ancestor in &ancestry.ancestors {
+        let launcher = ancestor
+            .launcher
+            .as_ref()
+            .map(|launcher| format!("  [{:?}: {}]", launcher.kind, launcher.name))
+            .unwrap_or_default();
+        println!("{:>7}  {}{}  {}", ancestor.pid, ancestor.name, launcher, ancestor.command);
+        if let Some(cwd) = &ancestor.working_dir {
+            println!("           in {}", cwd);
+        }
+    }
+
+    if let Some(launcher) = &ancestry.launched_by {
+        println!("\nLaunched from {}", launcher.name);
+    }
+
+    Ok(())
+}
+
+fn impact(args: &[String]) -> Result<()> {
+    let pid = parse_pid(args.first())?;
+    let impact = PortMonitor::new().get_kill_impact(pid)?;
+
+    println!("{}", impact.summary);
+
+    if !impact.listening_ports.is_empty() {
+        let ports: Vec<String> = impact.listening_ports.iter().map(u16::to_string).collect();
+        println!("\nListening on: {}", ports.join(", "));
+    }
+
+    if !impact.clients.is_empty() {
+        println!("\nClients:");
+        for client in &impact.clients {
+            let who = match (&client.process_name, client.pid) {
+                (Some(name), Some(pid)) => format!("{} (PID {})", name, pid),
+                _ if client.local => "local process".to_string(),
+                _ => "remote".to_string(),
+            };
+            println!("  :{:<5} <- {}:{}  {}", client.port, client.remote_address, client.remote_port, who);
+        }
+    }
+
+    if !impact.children.is_empty() {
+        println!("\nChild processes:");
+        for child in &impact.children {
+            println!("  {:>7}  (parent {})  {}", child.pid, child.parent_pid, child.command);
+        }
+    }
+
+    Ok(())
+}
+
+fn pause(args: &[String]) -> Result<()> {
+    let pid = parse_pid(args.first())?;
+    let method = match args.get(1).map(String::as_str) {
+        None => PauseMethod::Signal,
+        Some("--cgroup") => PauseMethod::CgroupFreeze,
+        Some(other) => return Err(anyhow!("Unknown option for pause: {}", other)),
+    };
+
+    let entry = ProcessManager::new().pause_process(pid, method)?;
+    println!("Paused {} (PID {})", entry.name, entry.pid);
+    print_pause_warning(&entry);
+
+    Ok(())
+}
+
+fn resume(args: &[String]) -> Result<()> {
+    let pid = parse_pid(args.first())?;
+    ProcessManager::new().resume_process(pid)?;
+    println!("Resumed PID {}", pid);
+    Ok(())
+}
+
+fn paused() -> Result<()> {
+    let entries = ProcessManager::new().paused_processes()?;
+
+    if entries.is_empty() {
+        println!("No processes paused by Porter");
+    }
+    for entry in &entries {
+        println!(
+            "{:>7}  {:<20} paused {}",
+            entry.pid,
+            entry.name,
+            entry.paused_at.format("%Y-%m-%d %H:%M:%S")
+        );
+        print_pause_warning(entry);
+    }
+
+    Ok(())
+}
+
+fn print_pause_warning(entry: &PausedProcess) {
+    if let Some(warning) = &entry.warning {
+        println!("  warning: {}", warning);
+    }
+}
+
+fn diagnose(args: &[String]) -> Result<()> {
+    let port = parse_port(args.first())?;
+    let diagnosis = PortMonitor::new().diagnose_port(port)?;
+
+    match args.get(1).map(String::as_str) {
+        Some("--json") => {
+            println!("{}", serde_json::to_string_pretty(&diagnosis)?);
+            return Ok(());
+        }
+        Some(other) => return Err(anyhow!("Unknown option for diagnose: {}", other)),
+        None => {}
+    }
+
+    println!("{}", diagnosis.summary);
+
+    for finding in &diagnosis.findings {
+        println!();
+        println!("{} {}", if finding.blocking { "!" } else { "-" }, finding.summary);
+        for fix in &finding.fixes {
+            println!("    fix: {}", fix);
+        }
+    }
+
+    println!();
+    println!("Bind attempts:");
+    for attempt in &diagnosis.bind_attempts {
+        println!(
+            "  {:<18} {:<16} {:?}{}",
+            attempt.address,
+            if attempt.reuse_address { "SO_REUSEADDR" } else { "plain" },
+            attempt.outcome,
+            attempt.error.as_ref().map(|e| format!(" ({})", e)).unwrap_or_default()
+        );
+    }
+
+    if !diagnosis.alternatives.is_empty() {
+        let alternatives: Vec<String> = diagnosis.alternatives.iter().map(u16::to_string).collect();
+        println!();
+        println!("Free ports nearby: {}", alternatives.join(", "));
+    }
+
+    Ok(())
+}
+
+fn connections(args: &[String]) -> Result<()> {
+    let port = parse_port(args.first())?;
+    let connections = PortMonitor::new().get_connection_details(port)?;
+
+    if connections.is_empty() {
+        println!("No sockets on port {}", port);
+    }
+
+    for conn in &connections {
+        let Some(tcp) = &conn.tcp else {
+            println!(
+                "{:<12} {}:{} -> {}:{}  {:?}",
+                conn.state, conn.local_address, conn.local_port, conn.remote_address, conn.remote_port, conn.protocol
+            );
+            continue;
+        };
+        let extra = match (&tcp.info, tcp.max_backlog) {
+            (_, Some(backlog)) => format!("  backlog {}", backlog),
+            (Some(info), None) => {
+                format!("  rtt {:.1}ms cwnd {}", info.rtt_us as f64 / 1000.0, info.snd_cwnd)
+            }
+            (None, None) => String::new(),
+        };
+
+        println!(
+            "{:<12} {}:{} -> {}:{}  tx {} rx {}  timer {:?} ({}ms) retrans {}{}",
+            conn.state,
+            conn.local_address,
+            conn.local_port,
+            conn.remote_address,
+            conn.remote_port,
+            tcp.tx_queue,
+            tcp.rx_queue,
+            tcp.timer,
+            tcp.timer_expires_ms,
+            tcp.retransmits,
+            extra
+        );
+    }
+
+    Ok(())
+}
+
+fn backlog(args: &[String]) -> Result<()> {
+    let mut args = args.iter();
+    let mut interval = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--watch" => {
+                let secs = value(&mut args, arg)?;
+                interval = Some(secs.parse::<u64>().map_err(|_| anyhow!("Invalid interval: {}", secs))?);
+            }
+            other => return Err(anyhow!("Unknown option for backlog: {}", other)),
+        }
+    }
+
+    let mut monitor = PortMonitor::new();
+    let report = monitor.get_backlog_report()?;
+
+    for listener in &report.listeners {
+        let owner = listener
+            .process
+            .as_ref()
+            .map(|p| format!("{} (PID {})", p.name, p.pid))
+            .unwrap_or_else(|| "-".to_string());
+        let limit = listener.max_backlog.map(|max| max.to_string()).unwrap_or_else(|| "?".to_string());
+
+        println!(
+            "{:>5}  {:<24} {:<24} {:>5} / {:<5}{}",
+            listener.port,
+            listener.local_address,
+            owner,
+            listener.queued,
+            limit,
+            if listener.filling { "  FILLING" } else { "" }
+        );
+    }
+
+    if let Some(counters) = report.counters.last() {
+        println!(
+            "\nListen overflows: {}  drops: {} (since boot)",
+            counters.listen_overflows, counters.listen_drops
+        );
+    }
+
+    let Some(interval) = interval else {
+        return Ok(());
+    };
+
+    loop {
+        std::thread::sleep(std::time::Duration::from_secs(interval));
+        let report = monitor.get_backlog_report()?;
+        let filling: Vec<String> = report
+            .listeners
+            .iter()
+            .filter(|l| l.filling)
+            .map(|l| format!("{} ({}/{})", l.port, l.queued, l.max_backlog.unwrap_or(0)))
+            .collect();
+
+        println!(
+            "{}  +{} overflows  filling: {}",
+            chrono::Local::now().format("%H:%M:%S"),
+            report.recent_overflows.unwrap_or(0),
+            if filling.is_empty() { "-".to_string() } else { filling.join(", ") }
+        );
+    }
+}
+
+fn leaks(args: &[String]) -> Result<()> {
+    let mut args = args.iter();
+    let mut interval = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--watch" => {
+                let secs = value(&mut args, arg)?;
+                interval = Some(secs.parse::<u64>().map_err(|_| anyhow!("Invalid interval: {}", secs))?);
+            }
+            other => return Err(anyhow!("Unknown option for leaks: {}", other)),
+        }
+    }
+
+    let mut monitor = PortMonitor::new();
+    let report = monitor.get_socket_leak_report()?;
+
+    for usage in &report.processes {
+        let fds = match (usage.open_fds, usage.fd_limit) {
+            (Some(open), Some(limit)) => format!("{} / {}", open, limit),
+            (Some(open), None) => open.to_string(),
+            _ => "?".to_string(),
+        };
+        let states: Vec<String> = usage
+            .sockets_by_state
+            .iter()
+            .map(|(state, count)| format!("{} {}", state, count))
+            .collect();
+
+        println!(
+            "{:>7}  {:<20} fds {:<14} {}",
+            usage.process.pid,
+            usage.process.name,
+            fds,
+            states.join(", ")
+        );
+        for warning in &usage.warnings {
+            println!("         ! {}", warning.message);
+        }
+    }
+
+    let Some(interval) = interval else {
+        return Ok(());
+    };
+
+    loop {
+        std::thread::sleep(std::time::Duration::from_secs(interval));
+        let report = monitor.get_socket_leak_report()?;
+        let now = chrono::Local::now().format("%H:%M:%S");
+        let mut quiet = true;
+
+        for usage in &report.processes {
+            for warning in &usage.warnings {
+                println!("{}  {} (PID {}): {}", now, usage.process.name, usage.process.pid, warning.message);
+                quiet = false;
+            }
+        }
+
+        if quiet {
+            println!("{}  no leaks", now);
+        }
+    }
+}
+
+fn print_interfaces() -> Result<()> {
+    for group in PortMonitor::new().get_ports_by_interface()? {
+        match &group.interface {
+            Some(iface) => {
+                let addresses: Vec<String> = iface
+                    .addresses
+                    .iter()
+                    .map(|addr| format!("{}/{}", addr.address, addr.prefix_len))
+                    .collect();
+                println!(
+                    "{} ({:?}, {})  {}",
+                    iface.name,
+                    iface.kind,
+                    if iface.up { "up" } else { "down" },
+                    addresses.join(" ")
+                );
+            }
+            None => println!("(bound to addresses no interface holds)"),
+        }
+
+        for port in &group.ports {
+            let owner = port
+                .process
+                .as_ref()
+                .map(|p| format!("{} (PID {})", p.name, p.pid))
+                .unwrap_or_else(|| "-".to_string());
+            println!("  {:>5}  {:<28} {}", port.port, port.ip_address, owner);
+        }
+    }
+
+    Ok(())
+}
+
+fn ephemeral(args: &[String]) -> Result<()> {
+    let mut service_ports = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--service" => service_ports.push(parse_port(Some(value(&mut args, arg)?))?),
+            other => return Err(anyhow!("Unknown option for ephemeral: {}", other)),
+        }
+    }
+
+    let range = EphemeralRange::read(&ProcFs::new());
+    match range.range() {
+        Some(r) => println!("Ephemeral range: {}-{}", r.start, r.end),
+        None => println!("Ephemeral range: unknown"),
+    }
+
+    // Scan the extra service ports first so their entries win the dedup below
+    let mut monitor = PortMonitor::new();
+    let mut ports = monitor.scan_ports(&service_ports)?;
+    ports.extend(monitor.get_active_ports()?);
+    ports.retain(|port| port.in_ephemeral_range || port.ephemeral_conflict);
+    ports.sort_by_key(|port| port.port);
+    ports.dedup_by_key(|port| port.port);
+    for port in &ports {
+        let note = if port.ephemeral_conflict {
+            "held by a client socket"
+        } else {
+            "in range"
+        };
+        let alternatives: Vec<String> = port.alternatives.iter().map(u16::to_string).collect();
+        let line = format!(
+            "  {:>5}  {:<9} {:<24} {}",
+            port.port,
+            format!("{:?}", port.status).to_lowercase(),
+            note,
+            if alternatives.is_empty() {
+                String::new()
+            } else {
+                format!("try {}", alternatives.join(", "))
+            }
+        );
+        println!("{}", line.trim_end());
+    }
+
+    Ok(())
+}
+
+fn print_service_graph(args: &[String]) -> Result<()> {
+    let graph = PortMonitor::new().get_service_graph()?;
+
+    match args.first().map(String::as_str) {
+        Some("--dot") => print!("{}", service_graph::render_dot(&graph)),
+        Some("--mermaid") => print!("{}", service_graph::render_mermaid(&graph)),
+        Some("--json") => println!("{}", serde_json::to_string_pretty(&graph)?),
+        Some(other) => return Err(anyhow!("Unknown option for graph: {}", other)),
+        None => {
+            if graph.edges.is_empty() {
+                println!("No connections between local services");
+            }
+
+            let name = |id: &str| {
+                graph
+                    .nodes
+                    .iter()
+                    .find(|node| node.id == id)
+                    .map(|node| match node.pid {
+                        Some(pid) => format!("{} (PID {})", node.name, pid),
+                        None => node.name.clone(),
+                    })
+                    .unwrap_or_else(|| id.to_string())
+            };
+            for edge in &graph.edges {
+                println!(
+                    "{} -> {} on :{}  ({} connection{})",
+                    name(&edge.from),
+                    name(&edge.to),
+                    edge.port,
+                    edge.connections,
+                    if edge.connections == 1 { "" } else { "s" }
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn print_exposure(args: &[String]) -> Result<()> {
+    let report = PortMonitor::new().get_exposure_report()?;
+
+    match args.first().map(String::as_str) {
+        Some("--json") => println!("{}", serde_json::to_string_pretty(&report)?),
+        Some("--markdown") => print!("{}", exposure::render_markdown(&report)),
+        Some(other) => return Err(anyhow!("Unknown option for exposure: {}", other)),
+        None => {
+            for entry in &report.listeners {
+                println!(
+                    "{:>5}  {:<28} {:<28} {:<20} {}{}",
+                    entry.port,
+                    entry.local_address,
+                    exposure::exposure_label(entry),
+                    entry.service.as_deref().unwrap_or("-"),
+                    entry.process_name.as_deref().unwrap_or("-"),
+                    if entry.flagged { "  EXPOSED" } else { "" }
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn sockets(args: &[String]) -> Result<()> {
+    let mut all = false;
+    let mut stale_only = false;
+
+    for arg in args {
+        match arg.as_str() {
+            "--all" => all = true,
+            "--stale" => stale_only = true,
+            other => return Err(anyhow!("Unknown option for sockets: {}", other)),
+        }
+    }
+
+    let sockets = PortMonitor::new().get_unix_sockets(all)?;
+
+    for socket in sockets.iter().filter(|s| !stale_only || s.stale) {
+        let owner = socket
+            .process
+            .as_ref()
+            .map(|p| format!("{} (PID {})", p.name, p.pid))
+            .unwrap_or_else(|| "-".to_string());
+
+        println!(
+            "{:<14} {:<24} {}{}",
+            socket.state,
+            owner,
+            socket.path.as_deref().unwrap_or("(unnamed)"),
+            if socket.stale { "  (deleted)" } else { "" }
+        );
+    }
+
+    Ok(())
+}
+
+fn other_sockets() -> Result<()> {
+    let sockets = PortMonitor::new().get_other_sockets()?;
+
+    for socket in &sockets {
+        let owner = socket
+            .process
+            .as_ref()
+            .map(|p| format!("{} (PID {})", p.name, p.pid))
+            .unwrap_or_else(|| "-".to_string());
+        let detail = match socket.protocol {
+            Protocol::Raw => socket.ip_protocol.map(|p| format!("ip proto {}", p)),
+            Protocol::Packet => socket.ethertype.map(|t| {
+                format!("ethertype 0x{:04x} on {}", t, socket.interface.as_deref().unwrap_or("any"))
+            }),
+            _ => socket.port.map(|p| format!("port {}", p)),
+        };
+
+        println!(
+            "{:<7} {:<24} {:<32} {}",
+            format!("{:?}", socket.protocol).to_uppercase(),
+            owner,
+            socket.local_address,
+            detail.unwrap_or_default()
+        );
+    }
+
+    Ok(())
+}
+
+fn kill_socket(args: &[String]) -> Result<()> {
+    let path = args.first().ok_or_else(|| anyhow!("Missing socket path"))?;
+    ProcessManager::new().kill_process_by_socket(path)?;
+    println!("Killed the process listening on {}", path);
+    Ok(())
+}
+
+fn kill_privileged(args: &[String]) -> Result<()> {
+    let pid = parse_pid(args.first())?;
+    let port = parse_port(args.get(1))?;
+    ProcessManager::new().kill_process_privileged(pid, port)?;
+    println!("Killed PID {} holding port {}", pid, port);
+    Ok(())
+}
+
+fn audit(args: &[String]) -> Result<()> {
+    let mut args = args.iter();
+    let mut limit = 50;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--limit" => {
+                let n = value(&mut args, arg)?;
+                limit = n.parse().map_err(|_| anyhow!("Invalid limit: {}", n))?;
+            }
+            other => return Err(anyhow!("Unknown option for audit: {}", other)),
+        }
+    }
+
+    let entries = ProcessManager::new().audit_entries(Some(limit))?;
+    if entries.is_empty() {
+        println!("No kill attempts recorded");
+    }
+
+    for entry in &entries {
+        let target = entry
+            .target
+            .as_ref()
+            .map(|t| format!("{} (PID {})", t.name, t.pid))
+            .or_else(|| entry.pid.map(|pid| format!("PID {}", pid)))
+            .unwrap_or_else(|| "-".to_string());
+        let port = entry.port.map(|p| format!(" port {}", p)).unwrap_or_default();
+        let outcome = match (&entry.error, entry.success) {
+            (_, true) => "ok",
+            (Some(error), false) => error.as_str(),
+            (None, false) => "failed",
+        };
+
+        println!(
+            "{}  {:<10} {:<24} {}{}  [{}]  {}",
+            entry.timestamp.format("%Y-%m-%d %H:%M:%S"),
+            entry.user,
+            entry.action,
+            target,
+            port,
+            entry.signals.join(", "),
+            outcome
+        );
+    }
+
+    Ok(())
+}
+
+fn reserve(args: &[String]) -> Result<()> {
+    let ports = parse_port_ranges(args)?;
+    print_reservations(reservations::reserve_ports(&ProcFs::new(), &ports)?)
+}
+
+fn unreserve(args: &[String]) -> Result<()> {
+    let ports = parse_port_ranges(args)?;
+    print_reservations(reservations::release_ports(&ProcFs::new(), &ports)?)
+}
+
+fn print_reservations(reservations: PortReservations) -> Result<()> {
+    let list = procfs::format_port_list(&reservations.reserved);
+    println!("Reserved ports:   {}", if list.is_empty() { "none" } else { &list });
+    if let Some(range) = reservations.ephemeral_port_range {
+        println!("Ephemeral ports:  {}-{}", range.start, range.end);
+    }
+    if !reservations.writable {
+        println!("(read-only: changing reservations needs root)");
+    }
+    Ok(())
+}
+
+fn print_system_info() -> Result<()> {
+    let info = system_info::collect(&ProcFs::new());
+    let net = &info.network;
+    let show = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+
+    println!("OS:               {} ({})", info.os_version, info.os);
+    println!("Hostname:         {}", info.hostname);
+    println!("Uptime:           {}h {}m", info.uptime_secs / 3600, info.uptime_secs % 3600 / 60);
+    println!(
+        "Ephemeral ports:  {}",
+        show(net.ephemeral_port_range.map(|r| format!("{}-{}", r.start, r.end)))
+    );
+    let reserved: Vec<String> = net
+        .reserved_ports
+        .iter()
+        .map(|r| if r.start == r.end { r.start.to_string() } else { format!("{}-{}", r.start, r.end) })
+        .collect();
+    println!("Reserved ports:   {}", if reserved.is_empty() { "-".to_string() } else { reserved.join(",") });
+    println!("somaxconn:        {}", show(net.somaxconn.map(|v| v.to_string())));
+    println!(
+        "TIME_WAIT:        tw_reuse={} fin_timeout={} max_buckets={}",
+        show(net.tcp_tw_reuse.map(|v| v.to_string())),
+        show(net.tcp_fin_timeout.map(|v| format!("{}s", v))),
+        show(net.tcp_max_tw_buckets.map(|v| v.to_string()))
+    );
+    println!(
+        "File handles:     {} open of {} max; nofile limit {}/{}",
+        show(net.files_open.map(|v| v.to_string())),
+        show(net.file_max.map(|v| v.to_string())),
+        show(net.fd_limit_soft.map(|v| v.to_string())),
+        show(net.fd_limit_hard.map(|v| v.to_string()))
+    );
+    if let Some(sockets) = &net.sockets {
+        println!(
+            "Sockets:          {} used; TCP {} in use, {} TIME_WAIT, {} orphaned; UDP {}; TCP6 {}; UDP6 {}",
+            sockets.sockets_used,
+            sockets.tcp_in_use,
+            sockets.tcp_time_wait,
+            sockets.tcp_orphaned,
+            sockets.udp_in_use,
+            sockets.tcp6_in_use,
+            sockets.udp6_in_use
+        );
+    }
+
+    Ok(())
+}
+
+fn print_diagnostics() -> Result<()> {
+    let report = diagnostics::collect()?;
+
+    println!("OS:        {}", report.os);
+    println!("Backend:   {}", report.backend);
+    println!("Elevated:  {}", if report.elevated { "yes" } else { "no" });
+    for capability in &report.capabilities {
+        println!(
+            "  {:<20} {}",
+            capability.name,
+            if capability.effective { "yes" } else { "no" }
+        );
+    }
+    if let Some(mode) = &report.hidepid {
+        println!("hidepid:   {}", mode);
+    }
+    println!("Unreadable processes: {}", report.unreadable_processes);
+    println!("Sockets:   {}", report.total_sockets);
+    for entry in &report.unattributed {
+        println!("  unattributed ({:?}): {}", entry.reason, entry.count);
+    }
+    for note in &report.notes {
+        println!("note: {}", note);
+    }
+
+    Ok(())
+}
+
+fn print_launch_spec(spec: &LaunchSpec) {
+    println!("  command: {} {}", spec.program, spec.args.join(" "));
+    if let Some(dir) = &spec.working_dir {
+        println!("  cwd:     {}", dir);
+    }
+    println!("  env:     {} variables", spec.env.len());
+}
+
+fn parse_port(arg: Option<&String>) -> Result<u16> {
+    let arg = arg.ok_or_else(|| anyhow!("Missing port\n\n{}", USAGE))?;
+    arg.parse().map_err(|_| anyhow!("Invalid port: {}", arg))
+}
+
+/// Ports and ranges in any mix of arguments and commas: `8080 9000-9010,3000`
+fn parse_port_ranges(args: &[String]) -> Result<Vec<PortRange>> {
+    let items: Vec<&str> = args.iter().flat_map(|arg| arg.split(',')).collect();
+    if items.is_empty() {
+        return Err(anyhow!("Missing ports\n\n{}", USAGE));
+    }
+
+    items
+        .into_iter()
+        .map(|item| match procfs::parse_port_list(item).as_slice() {
+            [range] => Ok(*range),
+            _ => Err(anyhow!("Invalid port or range: {}", item)),
+        })
+        .collect()
+}
+
+fn parse_pid(arg: Option<&String>) -> Result<u32> {
+    let arg = arg.ok_or_else(|| anyhow!("Missing PID\n\n{}", USAGE))?;
+    arg.parse().map_err(|_| anyhow!("Invalid PID: {}", arg))
+}
+
+fn value<'a>(args: &mut std::slice::Iter<'a, String>, flag: &str) -> Result<&'a String> {
+    args.next().ok_or_else(|| anyhow!("{} expects a value", flag))
+}
@@ -1,5 +1,15 @@
-use crate::models::{Port, SystemInfo};
-use crate::services::{PortMonitor, ProcessManager, admin};
+use crate::error::{CommandError, PorterError};
+use crate::models::{
+    AuditEntry, BacklogReport, ConnectionDetails, Diagnostics, ExposureReport, GraphFormat,
+    InterfacePorts, KillImpact, LaunchSpec, OtherSocket, PauseMethod, PausedProcess, Port, PortDiagnosis,
+    PortRange, PortReservations, ProcessAncestry, ProcessSockets, ReportFormat, RestartOptions, RestartResult,
+    ServiceGraph, SocketLeakReport, SystemInfo, UnixSocket, UserPorts,
+};
+use crate::services::systemd::UnitAction;
+use crate::services::{
+    PortMonitor, ProcFs, ProcessManager, admin, diagnostics, exposure, process_manager, reservations,
+    service_graph, system_info,
+};
 use std::sync::Mutex;
 use tauri::State;
 
@@ -18,18 +28,50 @@ impl AppState {
 }
 
 #[tauri::command]
-pub async fn get_active_ports(state: State<'_, AppState>) -> Result<Vec<Port>, String> {
+pub async fn get_active_ports(
+    state: State<'_, AppState>,
+    user: Option<String>,
+) -> Result<Vec<Port>, CommandError> {
+    let mut monitor = state.port_monitor.lock().unwrap();
+    match user {
+        Some(user) => monitor.get_ports_by_user(&user).map_err(CommandError::from),
+        None => monitor.get_active_ports().map_err(CommandError::from),
+    }
+}
+
+/// Include sockets from other network namespaces (containers, `ip netns`) in later scans
+#[tauri::command]
+pub async fn set_namespace_scanning(enabled: bool, state: State<'_, AppState>) -> Result<(), CommandError> {
+    state.port_monitor.lock().unwrap().set_scan_namespaces(enabled);
+    Ok(())
+}
+
+/// Ports services are expected on; client sockets holding one are flagged as conflicts
+#[tauri::command]
+pub async fn set_service_ports(ports: Vec<u16>, state: State<'_, AppState>) -> Result<(), CommandError> {
+    state.port_monitor.lock().unwrap().set_service_ports(&ports);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_ports_grouped_by_user(state: State<'_, AppState>) -> Result<Vec<UserPorts>, CommandError> {
     let mut monitor = state.port_monitor.lock().unwrap();
-    monitor.get_active_ports().map_err(|e| e.to_string())
+    monitor.get_ports_grouped_by_user().map_err(CommandError::from)
 }
 
 #[tauri::command]
-pub async fn get_common_ports(state: State<'_, AppState>, ports: Option<Vec<u16>>) -> Result<Vec<Port>, String> {
+pub async fn get_ports_by_interface(state: State<'_, AppState>) -> Result<Vec<InterfacePorts>, CommandError> {
+    let mut monitor = state.port_monitor.lock().unwrap();
+    monitor.get_ports_by_interface().map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn get_common_ports(state: State<'_, AppState>, ports: Option<Vec<u16>>) -> Result<Vec<Port>, CommandError> {
     let mut monitor = state.port_monitor.lock().unwrap();
     if let Some(custom_ports) = ports {
-        monitor.scan_ports(&custom_ports).map_err(|e| e.to_string())
+        monitor.scan_ports(&custom_ports).map_err(CommandError::from)
     } else {
-        monitor.scan_common_ports().map_err(|e| e.to_string())
+        monitor.scan_common_ports().map_err(CommandError::from)
     }
 }
 
@@ -37,46 +79,281 @@ pub async fn get_common_ports(state: State<'_, AppState>, ports: Option<Vec<u16>
 pub async fn get_port_details(
     port: u16,
     state: State<'_, AppState>,
-) -> Result<Option<Port>, String> {
+) -> Result<Option<Port>, CommandError> {
+    let mut monitor = state.port_monitor.lock().unwrap();
+    monitor.get_port_details(port).map_err(CommandError::from)
+}
+
+/// Explain why a port can't be bound ("address already in use") and how to fix it
+#[tauri::command]
+pub async fn diagnose_port(port: u16, state: State<'_, AppState>) -> Result<PortDiagnosis, CommandError> {
+    let mut monitor = state.port_monitor.lock().unwrap();
+    monitor.diagnose_port(port).map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn get_connection_details(
+    port: u16,
+    state: State<'_, AppState>,
+) -> Result<Vec<ConnectionDetails>, CommandError> {
+    let monitor = state.port_monitor.lock().unwrap();
+    monitor.get_connection_details(port).map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn get_backlog_report(state: State<'_, AppState>) -> Result<BacklogReport, CommandError> {
+    let mut monitor = state.port_monitor.lock().unwrap();
+    monitor.get_backlog_report().map_err(CommandError::from)
+}
+
+/// Per-process socket and descriptor usage; poll it to build up leak trends
+#[tauri::command]
+pub async fn get_socket_leak_report(state: State<'_, AppState>) -> Result<SocketLeakReport, CommandError> {
+    let mut monitor = state.port_monitor.lock().unwrap();
+    monitor.get_socket_leak_report().map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn get_exposure_report(state: State<'_, AppState>) -> Result<ExposureReport, CommandError> {
+    let mut monitor = state.port_monitor.lock().unwrap();
+    monitor.get_exposure_report().map_err(CommandError::from)
+}
+
+/// Render the exposure report as text the frontend can save to a file
+#[tauri::command]
+pub async fn export_exposure_report(
+    format: ReportFormat,
+    state: State<'_, AppState>,
+) -> Result<String, CommandError> {
+    let mut monitor = state.port_monitor.lock().unwrap();
+    let report = monitor.get_exposure_report().map_err(CommandError::from)?;
+
+    match format {
+        ReportFormat::Json => serde_json::to_string_pretty(&report).map_err(|e| {
+            PorterError::Internal {
+                detail: e.to_string(),
+            }
+            .into()
+        }),
+        ReportFormat::Markdown => Ok(exposure::render_markdown(&report)),
+    }
+}
+
+#[tauri::command]
+pub async fn get_service_graph(state: State<'_, AppState>) -> Result<ServiceGraph, CommandError> {
+    let mut monitor = state.port_monitor.lock().unwrap();
+    monitor.get_service_graph().map_err(CommandError::from)
+}
+
+/// Render the service graph as Graphviz DOT, Mermaid or JSON
+#[tauri::command]
+pub async fn export_service_graph(
+    format: GraphFormat,
+    state: State<'_, AppState>,
+) -> Result<String, CommandError> {
+    let mut monitor = state.port_monitor.lock().unwrap();
+    let graph = monitor.get_service_graph().map_err(CommandError::from)?;
+
+    match format {
+        GraphFormat::Json => serde_json::to_string_pretty(&graph).map_err(|e| {
+            PorterError::Internal {
+                detail: e.to_string(),
+            }
+            .into()
+        }),
+        GraphFormat::Dot => Ok(service_graph::render_dot(&graph)),
+        GraphFormat::Mermaid => Ok(service_graph::render_mermaid(&graph)),
+    }
+}
+
+#[tauri::command]
+pub async fn get_unix_sockets(
+    all: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<Vec<UnixSocket>, CommandError> {
+    let mut monitor = state.port_monitor.lock().unwrap();
+    monitor.get_unix_sockets(all.unwrap_or(false)).map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn get_other_sockets(state: State<'_, AppState>) -> Result<Vec<OtherSocket>, CommandError> {
+    let mut monitor = state.port_monitor.lock().unwrap();
+    monitor.get_other_sockets().map_err(CommandError::from)
+}
+
+/// Every socket `pid` has open, and optionally those of its descendants
+#[tauri::command]
+pub async fn get_process_sockets(
+    pid: u32,
+    include_descendants: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<ProcessSockets, CommandError> {
+    let mut monitor = state.port_monitor.lock().unwrap();
+    monitor
+        .get_process_sockets(pid, include_descendants.unwrap_or(false))
+        .map_err(CommandError::from)
+}
+
+/// The processes that started `pid`, nearest first, and the launcher it came from
+#[tauri::command]
+pub async fn get_process_ancestry(pid: u32, state: State<'_, AppState>) -> Result<ProcessAncestry, CommandError> {
     let mut monitor = state.port_monitor.lock().unwrap();
-    monitor.get_port_details(port).map_err(|e| e.to_string())
+    monitor.get_process_ancestry(pid).map_err(CommandError::from)
 }
 
+/// What killing `pid` would disrupt; call before confirming a kill
 #[tauri::command]
-pub async fn kill_process(pid: u32, state: State<'_, AppState>) -> Result<bool, String> {
+pub async fn get_kill_impact(pid: u32, state: State<'_, AppState>) -> Result<KillImpact, CommandError> {
+    let mut monitor = state.port_monitor.lock().unwrap();
+    monitor.get_kill_impact(pid).map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn kill_process(pid: u32, state: State<'_, AppState>) -> Result<bool, CommandError> {
     let mut manager = state.process_manager.lock().unwrap();
-    manager.kill_process(pid).map_err(|e| e.to_string())
+    manager.kill_process(pid).map_err(CommandError::from)
 }
 
 #[tauri::command]
-pub async fn kill_process_by_port(port: u16, state: State<'_, AppState>) -> Result<bool, String> {
+pub async fn kill_process_by_port(port: u16, state: State<'_, AppState>) -> Result<bool, CommandError> {
     let mut manager = state.process_manager.lock().unwrap();
     manager
         .kill_process_by_port(port)
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn kill_process_by_socket(path: String, state: State<'_, AppState>) -> Result<bool, CommandError> {
+    let mut manager = state.process_manager.lock().unwrap();
+    manager.kill_process_by_socket(&path).map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn kill_process_privileged(
+    pid: u32,
+    port: u16,
+    state: State<'_, AppState>,
+) -> Result<bool, CommandError> {
+    let mut manager = state.process_manager.lock().unwrap();
+    manager
+        .kill_process_privileged(pid, port)
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn stop_systemd_unit(pid: u32, state: State<'_, AppState>) -> Result<bool, CommandError> {
+    let mut manager = state.process_manager.lock().unwrap();
+    manager
+        .manage_systemd_unit(pid, UnitAction::Stop)
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn restart_systemd_unit(pid: u32, state: State<'_, AppState>) -> Result<bool, CommandError> {
+    let mut manager = state.process_manager.lock().unwrap();
+    manager
+        .manage_systemd_unit(pid, UnitAction::Restart)
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
-pub async fn get_system_info() -> Result<SystemInfo, String> {
-    use sysinfo::System;
+pub async fn stop_container(id: String, state: State<'_, AppState>) -> Result<bool, CommandError> {
+    let mut manager = state.process_manager.lock().unwrap();
+    manager.stop_container(&id, None).map_err(CommandError::from)
+}
 
-    let sys = System::new_all();
+#[tauri::command]
+pub async fn get_audit_log(
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<Vec<AuditEntry>, CommandError> {
+    let manager = state.process_manager.lock().unwrap();
+    manager.audit_entries(limit).map_err(CommandError::from)
+}
 
-    Ok(SystemInfo {
-        os: std::env::consts::OS.to_string(),
-        os_version: System::long_os_version().unwrap_or_else(|| "Unknown".to_string()),
-        hostname: System::host_name().unwrap_or_else(|| "Unknown".to_string()),
-        cpu_count: sys.cpus().len(),
-        total_memory: sys.total_memory(),
-    })
+#[tauri::command]
+pub async fn get_launch_spec(port: u16, state: State<'_, AppState>) -> Result<LaunchSpec, CommandError> {
+    let mut manager = state.process_manager.lock().unwrap();
+    manager.launch_spec_for_port(port).map_err(CommandError::from)
 }
 
 #[tauri::command]
-pub async fn is_elevated() -> Result<bool, String> {
+pub async fn restart_port_owner(
+    port: u16,
+    options: Option<RestartOptions>,
+    state: State<'_, AppState>,
+) -> Result<RestartResult, CommandError> {
+    let options = options.unwrap_or_default();
+    let timeout = process_manager::restart_timeout(&options);
+    // Waiting for the port to be released can take seconds; don't hold the lock meanwhile
+    let spec = {
+        let mut manager = state.process_manager.lock().unwrap();
+        manager.stop_port_owner(port, options)?
+    };
+    process_manager::relaunch(port, spec, timeout).map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn pause_process(
+    pid: u32,
+    method: Option<PauseMethod>,
+    state: State<'_, AppState>,
+) -> Result<PausedProcess, CommandError> {
+    let mut manager = state.process_manager.lock().unwrap();
+    manager
+        .pause_process(pid, method.unwrap_or(PauseMethod::Signal))
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn resume_process(pid: u32, state: State<'_, AppState>) -> Result<bool, CommandError> {
+    let mut manager = state.process_manager.lock().unwrap();
+    manager.resume_process(pid).map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn get_paused_processes(state: State<'_, AppState>) -> Result<Vec<PausedProcess>, CommandError> {
+    let mut manager = state.process_manager.lock().unwrap();
+    manager.paused_processes().map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn get_system_info() -> Result<SystemInfo, CommandError> {
+    Ok(system_info::collect(&ProcFs::new()))
+}
+
+#[tauri::command]
+pub async fn get_port_reservations() -> Result<PortReservations, CommandError> {
+    Ok(reservations::get_reservations(&ProcFs::new()))
+}
+
+/// Keep ports out of the ephemeral allocator until reboot (Linux, root only)
+#[tauri::command]
+pub async fn reserve_ports(ports: Vec<PortRange>) -> Result<PortReservations, CommandError> {
+    reservations::reserve_ports(&ProcFs::new(), &ports).map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn release_ports(ports: Vec<PortRange>) -> Result<PortReservations, CommandError> {
+    reservations::release_ports(&ProcFs::new(), &ports).map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn is_elevated() -> Result<bool, CommandError> {
     Ok(admin::is_elevated())
 }
 
 #[tauri::command]
-pub async fn request_elevation() -> Result<(), String> {
-    admin::request_elevation().map_err(|e| e.to_string())
+pub async fn request_elevation() -> Result<(), CommandError> {
+    admin::request_elevation().map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn has_privileged_helper() -> Result<bool, CommandError> {
+    Ok(admin::helper_path().is_some())
+}
+
+#[tauri::command]
+pub async fn get_diagnostics() -> Result<Diagnostics, CommandError> {
+    diagnostics::collect().map_err(CommandError::from)
 }
@@ -0,0 +1,135 @@
+use serde::Serialize;
+use thiserror::Error;
+
+/// Failures with a stable, machine-readable `code` the frontend can act on.
+/// Services raise these through `anyhow` so they survive `?` and `.context()`.
+#[derive(Debug, Error, Serialize)]
+#[serde(tag = "code", rename_all = "snake_case")]
+pub enum PorterError {
+    #[error("Failed to {operation} process (PID: {pid}). You may need sudo/root or administrator privileges.")]
+    PermissionDenied { pid: u32, operation: String },
+
+    #[error("Process not found: PID {pid}")]
+    ProcessNotFound { pid: u32 },
+
+    #[error("Port {port} is not in use")]
+    PortNotInUse { port: u16 },
+
+    #[error("No process is listening on {path}")]
+    SocketNotInUse { path: String },
+
+    #[error("Refusing to touch PID {pid}: {reason}")]
+    Protected { pid: u32, reason: String },
+
+    #[error("Timed out after {timeout_ms} ms waiting for {operation}")]
+    Timeout { operation: String, timeout_ms: u64 },
+
+    #[error("{backend} is unavailable: {reason}")]
+    BackendUnavailable { backend: String, reason: String },
+
+    #[error("{operation} is not supported on {os}")]
+    Unsupported { operation: String, os: String },
+
+    #[error("Authentication was cancelled or not authorized.")]
+    AuthenticationCancelled,
+
+    #[error("Please restart Porter with sudo/root privileges to kill system processes.")]
+    ElevationRequired,
+
+    #[error("{operation} requires sudo/root privileges.")]
+    PrivilegeRequired { operation: String },
+
+    #[error("PID {pid} was stopped but relaunching '{program}' failed: {reason}. The service is down.")]
+    RelaunchFailed { pid: u32, program: String, reason: String },
+
+    #[error("{detail}")]
+    InvalidInput { detail: String },
+
+    #[error("{detail}")]
+    Internal { detail: String },
+}
+
+impl PorterError {
+    pub fn permission_denied(pid: u32, operation: &str) -> Self {
+        Self::PermissionDenied {
+            pid,
+            operation: operation.to_string(),
+        }
+    }
+
+    pub fn unsupported(operation: &str) -> Self {
+        Self::Unsupported {
+            operation: operation.to_string(),
+            os: std::env::consts::OS.to_string(),
+        }
+    }
+
+    pub fn backend_unavailable(backend: &str, reason: impl ToString) -> Self {
+        Self::BackendUnavailable {
+            backend: backend.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+}
+
+/// Error returned by every Tauri command: the typed error plus its display message,
+/// e.g. `{ "code": "process_not_found", "pid": 42, "message": "Process not found: PID 42" }`
+#[derive(Debug, Serialize)]
+pub struct CommandError {
+    #[serde(flatten)]
+    pub error: PorterError,
+    pub message: String,
+}
+
+impl From<PorterError> for CommandError {
+    fn from(error: PorterError) -> Self {
+        Self {
+            message: error.to_string(),
+            error,
+        }
+    }
+}
+
+impl From<anyhow::Error> for CommandError {
+    fn from(err: anyhow::Error) -> Self {
+        match err.downcast::<PorterError>() {
+            Ok(error) => error.into(),
+            Err(err) => PorterError::Internal {
+                detail: err.to_string(),
+            }
+            .into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn serialize(error: impl Into<CommandError>) -> String {
+        serde_json::to_string(&error.into()).unwrap()
+    }
+
+    #[test]
+    fn context_fields_sit_beside_code_and_message() {
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&serialize(PorterError::ProcessNotFound { pid: 42 })).unwrap(),
+            json!({ "code": "process_not_found", "pid": 42, "message": "Process not found: PID 42" })
+        );
+    }
+
+    #[test]
+    fn message_only_variants_serialize_a_single_message_key() {
+        assert_eq!(
+            serialize(PorterError::InvalidInput {
+                detail: "No ports given".to_string(),
+            }),
+            r#"{"code":"invalid_input","detail":"No ports given","message":"No ports given"}"#
+        );
+        assert_eq!(
+            serialize(anyhow::anyhow!("disk full")),
+            r#"{"code":"internal","detail":"disk full","message":"disk full"}"#
+        );
+    }
+}
@@ -1,10 +1,17 @@
+mod cli;
 mod commands;
+mod error;
 mod models;
 mod platform;
 mod services;
 
 use commands::AppState;
 
+/// Run a CLI subcommand if the arguments name one, returning its exit code
+pub fn run_cli(args: &[String]) -> Option<i32> {
+    cli::run(args)
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
@@ -12,13 +19,46 @@ pub fn run() {
         .manage(AppState::new())
         .invoke_handler(tauri::generate_handler![
             commands::get_active_ports,
+            commands::get_ports_grouped_by_user,
+            commands::get_ports_by_interface,
+            commands::set_namespace_scanning,
+            commands::set_service_ports,
             commands::get_common_ports,
             commands::get_port_details,
+            commands::diagnose_port,
+            commands::get_connection_details,
+            commands::get_backlog_report,
+            commands::get_socket_leak_report,
+            commands::get_exposure_report,
+            commands::export_exposure_report,
+            commands::get_service_graph,
+            commands::export_service_graph,
+            commands::get_unix_sockets,
+            commands::get_other_sockets,
+            commands::get_process_sockets,
+            commands::get_process_ancestry,
+            commands::get_kill_impact,
             commands::kill_process,
             commands::kill_process_by_port,
+            commands::kill_process_by_socket,
+            commands::kill_process_privileged,
+            commands::stop_systemd_unit,
+            commands::restart_systemd_unit,
+            commands::stop_container,
+            commands::get_audit_log,
+            commands::get_launch_spec,
+            commands::restart_port_owner,
+            commands::pause_process,
+            commands::resume_process,
+            commands::get_paused_processes,
             commands::get_system_info,
+            commands::get_port_reservations,
+            commands::reserve_ports,
+            commands::release_ports,
             commands::is_elevated,
             commands::request_elevation,
+            commands::has_privileged_helper,
+            commands::get_diagnostics,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
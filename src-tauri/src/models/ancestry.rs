@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+use super::Process;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LauncherKind {
+    Terminal,
+    Ide,
+    Shell,
+    Multiplexer,
+    Supervisor,
+    Container,
+    Scheduler,
+    RemoteLogin,
+    Init,
+}
+
+/// A recognized program that starts other programs, e.g. `VS Code` or `tmux`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Launcher {
+    pub kind: LauncherKind,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AncestorProcess {
+    pub pid: u32,
+    pub name: String,
+    pub command: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub working_dir: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub launcher: Option<Launcher>,
+}
+
+/// The chain of processes that led to a process being started
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessAncestry {
+    pub process: Process,
+    /// Parent first, ending at init
+    pub ancestors: Vec<AncestorProcess>,
+    /// Nearest recognized launcher that isn't a shell, e.g. the terminal a shell runs in
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub launched_by: Option<Launcher>,
+}
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+
+/// Identity of the process a destructive action was aimed at
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditTarget {
+    pub pid: u32,
+    pub name: String,
+    pub command: String,
+    pub started_at: DateTime<Utc>,
+    /// Numeric owner, kept even when it has no name (e.g. a deleted account)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uid: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+}
+
+/// One attempt to kill a process, successful or not
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: DateTime<Utc>,
+    /// The user running Porter
+    pub user: String,
+    pub action: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pid: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<AuditTarget>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+    pub signals: Vec<String>,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
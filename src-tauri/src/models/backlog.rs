@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use super::Process;
+
+/// Accept-queue fill level of one listening socket
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListenerBacklog {
+    pub port: u16,
+    pub local_address: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub process: Option<Process>,
+    /// Connections completed by the kernel but not yet accepted
+    pub queued: u32,
+    /// Only known where the netlink backend is available
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_backlog: Option<u32>,
+    /// `queued / max_backlog`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub saturation: Option<f32>,
+    /// The queue is close to full, so new connections will soon be dropped
+    pub filling: bool,
+}
+
+/// System-wide `ListenOverflows` / `ListenDrops` from `/proc/net/netstat`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListenCounters {
+    pub timestamp: DateTime<Utc>,
+    pub listen_overflows: u64,
+    pub listen_drops: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BacklogReport {
+    pub listeners: Vec<ListenerBacklog>,
+    /// Samples taken on earlier reports, oldest first, ending with the current one
+    pub counters: Vec<ListenCounters>,
+    /// Overflows since the previous sample
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recent_overflows: Option<u64>,
+}
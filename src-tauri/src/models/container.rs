@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+use super::Protocol;
+
+/// The container behind a published host port
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContainerInfo {
+    /// `docker` or `podman`
+    pub runtime: String,
+    pub id: String,
+    pub name: String,
+    pub image: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compose_project: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compose_service: Option<String>,
+    /// Port inside the container that the host port forwards to
+    pub container_port: u16,
+}
+
+/// A host port a container runtime has published
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublishedPort {
+    pub host_ip: String,
+    pub host_port: u16,
+    pub protocol: Protocol,
+    pub container: ContainerInfo,
+}
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+use super::AttributionFailure;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Capability {
+    pub name: String,
+    pub effective: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnattributedCount {
+    pub reason: AttributionFailure,
+    pub count: usize,
+}
+
+/// What Porter can and cannot see with its current privileges
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Diagnostics {
+    pub os: String,
+    pub backend: String,
+    pub elevated: bool,
+    pub capabilities: Vec<Capability>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hidepid: Option<String>,
+    pub isolated_pid_namespace: bool,
+    /// Processes whose open sockets could not be inspected
+    pub unreadable_processes: usize,
+    pub total_sockets: usize,
+    pub unattributed: Vec<UnattributedCount>,
+    /// Human-readable hints on how to see more
+    pub notes: Vec<String>,
+}
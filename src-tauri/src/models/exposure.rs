@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use super::{InterfaceAddress, Protocol};
+
+/// Who can reach a listener, judged from the address it is bound to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Exposure {
+    /// 127.0.0.0/8 or ::1
+    LoopbackOnly,
+    /// A private, link-local or unique-local address on a physical, wireless or bridge interface
+    Lan,
+    /// A single address on any other interface, e.g. a public IP, VPN tunnel or container bridge
+    SpecificInterface,
+    /// 0.0.0.0 or ::, reachable on every interface including future ones
+    AllInterfaces,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExposureEntry {
+    pub port: u16,
+    pub protocol: Protocol,
+    pub local_address: String,
+    pub exposure: Exposure,
+    /// Interface owning `local_address`, for specific bindings
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interface: Option<String>,
+    /// Well-known service recognised by port or process name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pid: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub process_name: Option<String>,
+    /// A sensitive service reachable from beyond this machine
+    pub flagged: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExposureReport {
+    pub generated_at: DateTime<Utc>,
+    pub hostname: String,
+    pub interfaces: Vec<InterfaceAddress>,
+    /// Flagged entries first
+    pub listeners: Vec<ExposureEntry>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    Json,
+    Markdown,
+}
@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+use super::Port;
+
+/// One address assigned to a local network interface
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InterfaceAddress {
+    pub interface: String,
+    pub address: String,
+    pub prefix_len: u8,
+    pub loopback: bool,
+    pub up: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InterfaceKind {
+    Loopback,
+    /// Backed by hardware (Ethernet, or Wi-Fi where it cannot be told apart)
+    Physical,
+    Wireless,
+    /// docker0, br-*, podman and CNI bridges
+    ContainerBridge,
+    Bridge,
+    /// veth pairs, tun/tap devices, WireGuard and other software interfaces
+    Virtual,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkInterface {
+    pub name: String,
+    pub kind: InterfaceKind,
+    pub up: bool,
+    pub addresses: Vec<InterfaceAddress>,
+}
+
+/// Listeners reachable through one interface; wildcard binds appear under
+/// every interface they cover
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InterfacePorts {
+    /// `None` collects ports bound to an address no interface holds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interface: Option<NetworkInterface>,
+    pub ports: Vec<Port>,
+}
@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+use super::{Process, SystemdUnit};
+
+/// A connection to one of the target's listening ports
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImpactedClient {
+    /// The target's port the client is connected to
+    pub port: u16,
+    pub remote_address: String,
+    pub remote_port: u16,
+    /// The client is a process on this machine
+    pub local: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pid: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub process_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChildProcess {
+    pub pid: u32,
+    pub parent_pid: u32,
+    pub name: String,
+    pub command: String,
+}
+
+/// Something that may start the process again after it is killed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RestartManager {
+    Systemd { unit: SystemdUnit },
+    /// Killing the main process ends the container, and its restart policy may bring it back
+    Container {
+        runtime: String,
+        id: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+    },
+}
+
+/// What killing a process would disrupt, shown before the kill is confirmed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KillImpact {
+    pub process: Process,
+    pub listening_ports: Vec<u16>,
+    pub clients: Vec<ImpactedClient>,
+    /// Every descendant, children before grandchildren
+    pub children: Vec<ChildProcess>,
+    pub restarted_by: Vec<RestartManager>,
+    /// One-line description for a confirmation dialog
+    pub summary: String,
+}
@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+use super::Process;
+
+/// One process's socket and descriptor counts at one report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SocketUsageSample {
+    pub timestamp: DateTime<Utc>,
+    pub close_wait: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub open_fds: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LeakKind {
+    /// CLOSE_WAIT sockets keep piling up: the process isn't closing connections its peers closed
+    CloseWait,
+    /// Open descriptors are near, or growing toward, `RLIMIT_NOFILE`
+    FdExhaustion,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeakWarning {
+    pub kind: LeakKind,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessSocketUsage {
+    pub process: Process,
+    /// TCP sockets by state, e.g. `ESTABLISHED` or `CLOSE_WAIT`
+    pub sockets_by_state: BTreeMap<String, usize>,
+    pub close_wait: usize,
+    /// Not known where the process's descriptors can't be read
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub open_fds: Option<u64>,
+    /// Soft `RLIMIT_NOFILE`; EMFILE is returned past this
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fd_limit: Option<u64>,
+    /// `open_fds / fd_limit`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fd_usage: Option<f32>,
+    /// Samples taken on earlier reports, oldest first, ending with the current one
+    pub history: Vec<SocketUsageSample>,
+    pub warnings: Vec<LeakWarning>,
+}
+
+/// Per-process socket usage, processes with warnings first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SocketLeakReport {
+    pub generated_at: DateTime<Utc>,
+    pub processes: Vec<ProcessSocketUsage>,
+}
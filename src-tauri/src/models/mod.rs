@@ -1,3 +1,43 @@
+pub mod ancestry;
+pub mod audit;
+pub mod backlog;
+pub mod container;
+pub mod diagnostics;
+pub mod exposure;
+pub mod interface;
+pub mod kill_impact;
+pub mod leak;
+pub mod namespace;
+pub mod other_socket;
+pub mod pause;
 pub mod port;
+pub mod port_diagnosis;
+pub mod process_sockets;
+pub mod restart;
+pub mod service_graph;
+pub mod system;
+pub mod systemd;
+pub mod tcp;
+pub mod unix_socket;
 
+pub use ancestry::*;
+pub use audit::*;
+pub use backlog::*;
+pub use container::*;
+pub use diagnostics::*;
+pub use exposure::*;
+pub use interface::*;
+pub use kill_impact::*;
+pub use leak::*;
+pub use namespace::*;
+pub use other_socket::*;
+pub use pause::*;
 pub use port::*;
+pub use port_diagnosis::*;
+pub use process_sockets::*;
+pub use restart::*;
+pub use service_graph::*;
+pub use system::*;
+pub use systemd::*;
+pub use tcp::*;
+pub use unix_socket::*;
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+/// A Linux network namespace, identified by the inode of `/proc/<pid>/ns/net`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NetNamespace {
+    pub inode: u64,
+    /// Name given by `ip netns add`, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Whether this is the namespace Porter itself runs in
+    pub host: bool,
+}
@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+use super::{Process, Protocol};
+
+/// A raw, ICMP, packet or SCTP socket; these do not fit the port list
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OtherSocket {
+    pub protocol: Protocol,
+    /// Bound address; empty for packet sockets
+    pub local_address: String,
+    /// SCTP port, or the echo identifier of an ICMP socket
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+    /// IP protocol a raw socket receives (1 = ICMP, 6 = TCP, 255 = any)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip_protocol: Option<u8>,
+    /// EtherType a packet socket captures (0x0003 = all)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ethertype: Option<u16>,
+    /// Interface a packet socket is bound to; `None` means all
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interface: Option<String>,
+    pub inode: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub process: Option<Process>,
+}
@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PauseMethod {
+    /// SIGSTOP / SIGCONT on the process itself
+    Signal,
+    /// Freeze the process's whole cgroup (Linux cgroup v2 only)
+    CgroupFreeze,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PausedProcess {
+    pub pid: u32,
+    pub name: String,
+    pub method: PauseMethod,
+    pub paused_at: DateTime<Utc>,
+    /// Used to tell the paused process apart from a later one reusing its PID
+    pub started_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cgroup: Option<String>,
+    #[serde(default)]
+    pub listening_ports: Vec<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warning: Option<String>,
+}
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
+use super::{ContainerInfo, NetNamespace, NetworkInterface, NetworkLimits, SystemdUnit};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -7,12 +8,38 @@ pub enum PortStatus {
     Free,
     Occupied,
     System,
+    /// In use, but the owning process could not be determined
+    Unknown,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Why a socket could not be attributed to a process
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AttributionFailure {
+    /// Owned by the kernel rather than a process (e.g. TIME_WAIT)
+    NoOwner,
+    /// Other processes' file descriptors could not be read (EACCES)
+    PermissionDenied,
+    /// /proc is mounted with `hidepid`, hiding other users' processes
+    Hidepid,
+    /// The owner lives outside Porter's PID namespace
+    OtherNamespace,
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Protocol {
     TCP,
     UDP,
+    #[serde(rename = "RAW")]
+    Raw,
+    /// Unprivileged ping sockets
+    #[serde(rename = "ICMP")]
+    Icmp,
+    #[serde(rename = "PACKET")]
+    Packet,
+    #[serde(rename = "SCTP")]
+    Sctp,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -24,6 +51,23 @@ pub struct Port {
     pub ip_address: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub created_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unattributed: Option<AttributionFailure>,
+    /// Set when a container runtime publishes this port
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub container: Option<ContainerInfo>,
+    /// Only set when namespace scanning is enabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<NetNamespace>,
+    /// The kernel may hand this port to outgoing connections
+    #[serde(default)]
+    pub in_ephemeral_range: bool,
+    /// A configured service port currently held by an outgoing client socket
+    #[serde(default)]
+    pub ephemeral_conflict: bool,
+    /// Free ports outside the ephemeral range, offered for risky service ports
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub alternatives: Vec<u16>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,13 +76,37 @@ pub struct Process {
     pub name: String,
     pub path: String,
     pub command: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_pid: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub working_dir: Option<String>,
     pub cpu_usage: f32,
     pub memory_usage: u64,
     pub started_at: DateTime<Utc>,
+    /// Owning user's name, falling back to the numeric UID when it cannot be resolved
     #[serde(skip_serializing_if = "Option::is_none")]
     pub user: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uid: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gid: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub systemd_unit: Option<SystemdUnit>,
+    /// PID as seen inside the process's own PID namespace (e.g. inside a container)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace_pid: Option<u32>,
+}
+
+/// Ports owned by one user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserPorts {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uid: Option<u32>,
+    pub ports: Vec<Port>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,4 +116,7 @@ pub struct SystemInfo {
     pub hostname: String,
     pub cpu_count: usize,
     pub total_memory: u64,
+    pub uptime_secs: u64,
+    pub interfaces: Vec<NetworkInterface>,
+    pub network: NetworkLimits,
 }
@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+use super::NetNamespace;
+
+/// What happened when Porter tried to bind the port itself
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BindOutcome {
+    Bound,
+    /// `EADDRINUSE`
+    InUse,
+    /// `EACCES`, e.g. a privileged port without root
+    PermissionDenied,
+    /// `EADDRNOTAVAIL`, e.g. IPv6 disabled
+    AddressUnavailable,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BindAttempt {
+    /// `0.0.0.0:8080`, `[::1]:8080`, ...
+    pub address: String,
+    /// Whether `SO_REUSEADDR` was set first, as most servers do
+    pub reuse_address: bool,
+    pub outcome: BindOutcome,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Declared in the order findings are reported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagnosisCause {
+    /// A process is listening on the port
+    Listener,
+    /// Something listens on the port inside another network namespace
+    NamespaceListener,
+    /// Closed connections the kernel still remembers
+    TimeWait,
+    /// Connections the peer closed but the owner never did
+    CloseWait,
+    /// An outgoing connection was handed the port as its source port
+    EphemeralClient,
+    /// Binding needs root or `CAP_NET_BIND_SERVICE`
+    Privileged,
+    /// The kernel may hand the port to outgoing connections
+    EphemeralRange,
+    /// Listed in `ip_local_reserved_ports`
+    Reserved,
+}
+
+/// One thing found on or about the port
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiagnosisFinding {
+    pub cause: DiagnosisCause,
+    /// Whether this can make a bind fail, as opposed to background information
+    pub blocking: bool,
+    pub summary: String,
+    /// Sockets this finding covers, for the socket-based causes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sockets: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pid: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub process_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<NetNamespace>,
+    pub fixes: Vec<String>,
+}
+
+/// Why a port can or cannot be bound, most likely cause first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortDiagnosis {
+    pub port: u16,
+    /// Whether a typical server (with `SO_REUSEADDR`) could bind every address right now
+    pub bindable: bool,
+    pub summary: String,
+    pub findings: Vec<DiagnosisFinding>,
+    pub bind_attempts: Vec<BindAttempt>,
+    /// Free ports outside the ephemeral range
+    pub alternatives: Vec<u16>,
+}
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+use super::{ConnectionDetails, Process, UnixSocket};
+
+/// Everything one process has open on the network, for a process details page
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessSockets {
+    pub process: Process,
+    pub listeners: Vec<ConnectionDetails>,
+    /// Every other TCP socket, including TIME_WAIT and CLOSE_WAIT ones
+    pub connections: Vec<ConnectionDetails>,
+    /// `process` is left unset on these; the owner is the enclosing process
+    pub unix_sockets: Vec<UnixSocket>,
+    /// Each descendant with its own sockets, only when requested; always empty on the descendants themselves
+    pub descendants: Vec<ProcessSockets>,
+}
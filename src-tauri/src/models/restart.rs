@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// How a process was started, captured so it can be launched again
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaunchSpec {
+    pub pid: u32,
+    pub program: String,
+    pub args: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub working_dir: Option<String>,
+    pub env: HashMap<String, String>,
+    /// Owner to relaunch as, so an elevated Porter doesn't bring a user's service back as root
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uid: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gid: Option<u32>,
+}
+
+/// Edits applied to a captured `LaunchSpec` before relaunching
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RestartOptions {
+    /// Replaces the program and its arguments when set
+    pub command: Option<Vec<String>>,
+    pub working_dir: Option<String>,
+    /// Variables added to (or overriding) the captured environment
+    pub env: HashMap<String, String>,
+    /// Variables removed from the captured environment
+    pub unset_env: Vec<String>,
+    /// How long to wait for the port to be released before giving up
+    pub timeout_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestartResult {
+    pub port: u16,
+    pub old_pid: u32,
+    pub new_pid: u32,
+    pub spec: LaunchSpec,
+}
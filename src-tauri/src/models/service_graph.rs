@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+
+/// A local process taking part in at least one local connection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceNode {
+    /// `pid-1234`, or `port-5432` for a listener whose owner is hidden
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pid: Option<u32>,
+    pub name: String,
+    /// Every port the process listens on, not only the ones in use
+    pub listening_ports: Vec<u16>,
+}
+
+/// Client `from` holds connections to a listener of `to` on `port`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceEdge {
+    pub from: String,
+    pub to: String,
+    pub port: u16,
+    pub connections: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceGraph {
+    pub generated_at: DateTime<Utc>,
+    pub nodes: Vec<ServiceNode>,
+    pub edges: Vec<ServiceEdge>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphFormat {
+    Json,
+    Dot,
+    Mermaid,
+}
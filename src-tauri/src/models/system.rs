@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+
+/// Inclusive range of port numbers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PortRange {
+    pub start: u16,
+    pub end: u16,
+}
+
+impl PortRange {
+    pub fn contains(&self, port: u16) -> bool {
+        (self.start..=self.end).contains(&port)
+    }
+}
+
+/// Socket totals from `/proc/net/sockstat` and `sockstat6`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SocketStats {
+    pub sockets_used: u64,
+    pub tcp_in_use: u64,
+    pub tcp_orphaned: u64,
+    pub tcp_time_wait: u64,
+    pub tcp_allocated: u64,
+    pub udp_in_use: u64,
+    pub raw_in_use: u64,
+    pub tcp6_in_use: u64,
+    pub udp6_in_use: u64,
+}
+
+/// Kernel settings that explain port exhaustion, refused connections and EMFILE errors.
+/// Every field is `None` where the OS does not expose it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NetworkLimits {
+    /// `net.ipv4.ip_local_port_range`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ephemeral_port_range: Option<PortRange>,
+    /// `net.ipv4.ip_local_reserved_ports`
+    pub reserved_ports: Vec<PortRange>,
+    /// `net.core.somaxconn`, the cap on every listen backlog
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub somaxconn: Option<u32>,
+    /// `net.ipv4.tcp_tw_reuse`: 0 off, 1 on, 2 loopback only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tcp_tw_reuse: Option<u8>,
+    /// `net.ipv4.tcp_fin_timeout`, seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tcp_fin_timeout: Option<u32>,
+    /// `net.ipv4.tcp_max_tw_buckets`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tcp_max_tw_buckets: Option<u64>,
+    /// `fs.file-max`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_max: Option<u64>,
+    /// Allocated file handles system-wide (first field of `fs.file-nr`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub files_open: Option<u64>,
+    /// Porter's own `RLIMIT_NOFILE`, which child processes usually inherit
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fd_limit_soft: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fd_limit_hard: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sockets: Option<SocketStats>,
+}
+
+/// Ports kept out of the ephemeral allocator (`net.ipv4.ip_local_reserved_ports`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortReservations {
+    pub reserved: Vec<PortRange>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ephemeral_port_range: Option<PortRange>,
+    /// Whether this process may change the list; needs root on Linux
+    pub writable: bool,
+}
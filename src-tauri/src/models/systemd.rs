@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UnitScope {
+    /// Managed by the system instance (PID 1)
+    System,
+    /// Managed by a per-user `user@<uid>.service` instance
+    User,
+}
+
+/// The systemd unit a process runs under, read from its cgroup
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SystemdUnit {
+    pub name: String,
+    pub scope: UnitScope,
+    /// Owner of the user manager, for user units
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uid: Option<u32>,
+    /// Service units may be restarted by systemd when killed, so they should be
+    /// stopped through systemd instead. Scopes (terminals, sessions) are not.
+    pub managed: bool,
+}
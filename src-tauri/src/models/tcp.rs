@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+use super::Protocol;
+
+/// Which kernel timer is pending on a TCP socket (`tr` in `/proc/net/tcp`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TcpTimer {
+    Off,
+    Retransmit,
+    KeepAlive,
+    TimeWait,
+    ZeroWindowProbe,
+}
+
+/// Queue, timer and ownership fields of one TCP socket
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TcpDetails {
+    /// Unsent bytes
+    pub tx_queue: u32,
+    /// Unread bytes; for a listener, connections waiting to be accepted
+    pub rx_queue: u32,
+    /// Accept backlog limit of a listener, from the netlink backend
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_backlog: Option<u32>,
+    pub timer: TcpTimer,
+    pub timer_expires_ms: u64,
+    /// Unrecovered retransmissions (zero-window probes for `ZeroWindowProbe`)
+    pub retransmits: u32,
+    pub uid: u32,
+    pub inode: u64,
+    /// Only available where the netlink `sock_diag` backend works
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub info: Option<TcpInfo>,
+}
+
+/// Subset of the kernel's `struct tcp_info`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TcpInfo {
+    pub rtt_us: u32,
+    pub rtt_var_us: u32,
+    pub rto_us: u32,
+    pub snd_cwnd: u32,
+    pub snd_mss: u32,
+    pub unacked: u32,
+    pub lost: u32,
+    pub total_retrans: u32,
+    pub last_data_recv_ms: u32,
+    pub last_data_sent_ms: u32,
+    pub bytes_acked: u64,
+    pub bytes_received: u64,
+}
+
+/// One socket on a port, for the port details panel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectionDetails {
+    pub local_address: String,
+    pub local_port: u16,
+    pub remote_address: String,
+    pub remote_port: u16,
+    pub protocol: Protocol,
+    pub state: String,
+    pub pid: u32,
+    /// Only set for TCP sockets on backends that read the kernel's socket tables
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tcp: Option<TcpDetails>,
+}
@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+use super::Process;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UnixSocketType {
+    Stream,
+    Dgram,
+    Seqpacket,
+}
+
+/// A Unix domain socket, the socket-path counterpart of a `Port`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnixSocket {
+    /// Filesystem path, or `@name` for an abstract socket; unnamed sockets have none
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    pub socket_type: UnixSocketType,
+    pub state: String,
+    pub listening: bool,
+    pub inode: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub process: Option<Process>,
+    /// The socket file was deleted while the socket stayed open,
+    /// so new clients cannot reach this listener
+    pub stale: bool,
+}
@@ -1,25 +1,462 @@
-use super::{NetworkConnection, Protocol};
+use super::{
+    NetworkConnection, OtherSocketEntry, PlatformDiagnostics, Protocol, TcpDiag, UnixSocketEntry,
+};
+use crate::models::{AttributionFailure, NetNamespace, TcpDetails, TcpTimer, UnixSocketType};
 use anyhow::Result;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
+use std::io::ErrorKind;
+use std::os::unix::fs::MetadataExt;
 use std::path::Path;
 
 pub fn get_network_connections() -> Result<Vec<NetworkConnection>> {
+    Ok(connections_with_owners(&SocketOwners::scan()))
+}
+
+/// Connections and diagnostics from a single pass over `/proc`
+pub fn get_network_connections_with_diagnostics() -> Result<(Vec<NetworkConnection>, PlatformDiagnostics)> {
+    let owners = SocketOwners::scan();
+    Ok((connections_with_owners(&owners), platform_diagnostics(&owners)))
+}
+
+fn connections_with_owners(owners: &SocketOwners) -> Vec<NetworkConnection> {
     let mut connections = Vec::new();
 
     // Read TCP connections from /proc/net/tcp
-    if let Ok(tcp_conns) = parse_proc_net_tcp("/proc/net/tcp") {
+    if let Ok(tcp_conns) = parse_proc_net_tcp("/proc/net/tcp", owners) {
         connections.extend(tcp_conns);
     }
 
     // Read TCP6 connections
-    if let Ok(tcp6_conns) = parse_proc_net_tcp("/proc/net/tcp6") {
+    if let Ok(tcp6_conns) = parse_proc_net_tcp("/proc/net/tcp6", owners) {
         connections.extend(tcp6_conns);
     }
 
+    connections
+}
+
+/// Like `get_network_connections`, but reads the socket tables of every
+/// distinct network namespace through `/proc/<pid>/net`
+pub fn get_namespaced_connections() -> Result<Vec<NetworkConnection>> {
+    let mut connections = Vec::new();
+    // Socket inodes are unique system-wide, so one owner map serves every namespace
+    let owners = SocketOwners::scan();
+    let proc = Path::new("/proc");
+    let host = netns_inode(proc, "self");
+    let names = named_namespaces();
+
+    for (inode, pid) in distinct_namespaces(proc) {
+        let namespace = NetNamespace {
+            inode,
+            name: names.get(&inode).cloned(),
+            host: Some(inode) == host,
+        };
+
+        for table in ["tcp", "tcp6"] {
+            let path = format!("/proc/{}/net/{}", pid, table);
+            if let Ok(conns) = parse_proc_net_tcp(&path, &owners) {
+                connections.extend(conns.into_iter().map(|mut conn| {
+                    conn.namespace = Some(namespace.clone());
+                    conn
+                }));
+            }
+        }
+    }
+
     Ok(connections)
 }
 
-fn parse_proc_net_tcp(path: &str) -> Result<Vec<NetworkConnection>> {
+/// Unix domain sockets from `/proc/net/unix`
+pub fn get_unix_sockets() -> Result<Vec<UnixSocketEntry>> {
+    let content = fs::read_to_string("/proc/net/unix")?;
+    Ok(parse_proc_net_unix(&content, &SocketOwners::scan()))
+}
+
+fn parse_proc_net_unix(content: &str, owners: &SocketOwners) -> Vec<UnixSocketEntry> {
+    // Flag set on sockets that called listen()
+    const SO_ACCEPTCON: u32 = 0x10000;
+
+    let mut sockets = Vec::new();
+
+    // Num RefCount Protocol Flags Type St Inode [Path]
+    for line in content.lines().skip(1) {
+        let parts: Vec<&str> = line.split_whitespace().take(7).collect();
+        if parts.len() < 7 {
+            continue;
+        }
+
+        let flags = u32::from_str_radix(parts[3], 16).unwrap_or(0);
+        let socket_type = match parts[4] {
+            "0001" => UnixSocketType::Stream,
+            "0002" => UnixSocketType::Dgram,
+            "0005" => UnixSocketType::Seqpacket,
+            _ => continue,
+        };
+        let inode = parts[6].parse::<u64>().unwrap_or(0);
+        let listening = flags & SO_ACCEPTCON != 0;
+
+        let state = if listening {
+            "LISTEN"
+        } else {
+            match parts[5] {
+                "01" => "UNCONNECTED",
+                "02" => "CONNECTING",
+                "03" => "CONNECTED",
+                "04" => "DISCONNECTING",
+                _ => "UNKNOWN",
+            }
+        };
+
+        sockets.push(UnixSocketEntry {
+            path: unix_socket_path(line),
+            socket_type,
+            state: state.to_string(),
+            listening,
+            inode,
+            pid: owners.pid(inode),
+            shared_with: owners.shared_with(inode),
+        });
+    }
+
+    sockets
+}
+
+/// Raw, ICMP, packet and SCTP sockets; tables for protocols the kernel
+/// has not loaded simply do not exist and are skipped
+pub fn get_other_sockets() -> Result<Vec<OtherSocketEntry>> {
+    let owners = SocketOwners::scan();
+    let mut sockets = Vec::new();
+
+    for (path, protocol) in [
+        ("/proc/net/raw", Protocol::Raw),
+        ("/proc/net/raw6", Protocol::Raw),
+        ("/proc/net/icmp", Protocol::Icmp),
+        ("/proc/net/icmp6", Protocol::Icmp),
+    ] {
+        if let Ok(content) = fs::read_to_string(path) {
+            sockets.extend(parse_proc_net_inet(&content, protocol, &owners));
+        }
+    }
+
+    if let Ok(content) = fs::read_to_string("/proc/net/packet") {
+        sockets.extend(parse_proc_net_packet(&content, &interface_names(), &owners));
+    }
+
+    if let Ok(content) = fs::read_to_string("/proc/net/sctp/eps") {
+        sockets.extend(parse_proc_net_sctp(&content, &owners));
+    }
+
+    Ok(sockets)
+}
+
+/// Raw and ICMP tables share the TCP layout; the "port" of a raw socket
+/// is the IP protocol it is bound to
+fn parse_proc_net_inet(content: &str, protocol: Protocol, owners: &SocketOwners) -> Vec<OtherSocketEntry> {
+    content
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            if parts.len() < 10 {
+                return None;
+            }
+
+            let (addr, number) = parts[1].split_once(':')?;
+            let number = u16::from_str_radix(number, 16).ok()?;
+            let inode = parts[9].parse::<u64>().unwrap_or(0);
+            let raw = protocol == Protocol::Raw;
+
+            Some(OtherSocketEntry {
+                local_address: parse_hex_address(addr),
+                port: (!raw).then_some(number),
+                ip_protocol: if raw { u8::try_from(number).ok() } else { None },
+                ethertype: None,
+                interface: None,
+                inode,
+                pid: owners.pid(inode),
+                protocol,
+            })
+        })
+        .collect()
+}
+
+/// sk RefCnt Type Proto Iface R Rmem User Inode
+fn parse_proc_net_packet(
+    content: &str,
+    interfaces: &HashMap<u32, String>,
+    owners: &SocketOwners,
+) -> Vec<OtherSocketEntry> {
+    content
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            if parts.len() < 9 {
+                return None;
+            }
+
+            let ifindex = parts[4].parse::<u32>().unwrap_or(0);
+            let inode = parts[8].parse::<u64>().unwrap_or(0);
+
+            Some(OtherSocketEntry {
+                protocol: Protocol::Packet,
+                local_address: String::new(),
+                port: None,
+                ip_protocol: None,
+                ethertype: u16::from_str_radix(parts[3], 16).ok(),
+                // Index 0 means the socket is not bound to one interface
+                interface: (ifindex != 0).then(|| {
+                    interfaces
+                        .get(&ifindex)
+                        .cloned()
+                        .unwrap_or_else(|| format!("if{}", ifindex))
+                }),
+                inode,
+                pid: owners.pid(inode),
+            })
+        })
+        .collect()
+}
+
+/// ENDPT SOCK STY SST HBKT LPORT UID INODE LADDRS...
+fn parse_proc_net_sctp(content: &str, owners: &SocketOwners) -> Vec<OtherSocketEntry> {
+    content
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            if parts.len() < 8 {
+                return None;
+            }
+
+            let inode = parts[7].parse::<u64>().unwrap_or(0);
+
+            Some(OtherSocketEntry {
+                protocol: Protocol::Sctp,
+                // Multi-homed endpoints list every bound address
+                local_address: parts[8..].join(" "),
+                port: parts[5].parse().ok(),
+                ip_protocol: None,
+                ethertype: None,
+                interface: None,
+                inode,
+                pid: owners.pid(inode),
+            })
+        })
+        .collect()
+}
+
+/// Interface index to name, from `/sys/class/net/<name>/ifindex`
+fn interface_names() -> HashMap<u32, String> {
+    let Ok(entries) = fs::read_dir("/sys/class/net") else {
+        return HashMap::new();
+    };
+
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let index = fs::read_to_string(entry.path().join("ifindex")).ok()?;
+            Some((index.trim().parse().ok()?, entry.file_name().to_string_lossy().into_owned()))
+        })
+        .collect()
+}
+
+/// Paths may contain spaces, so the path is everything after the seventh field
+fn unix_socket_path(line: &str) -> Option<String> {
+    let mut rest = line;
+    for _ in 0..7 {
+        rest = rest.trim_start();
+        rest = &rest[rest.find(char::is_whitespace).unwrap_or(rest.len())..];
+    }
+
+    let path = rest.trim_start();
+    (!path.is_empty()).then(|| path.to_string())
+}
+
+/// Inode of the network namespace `<proc>/<pid>/ns/net` points at
+fn netns_inode(proc: &Path, pid: &str) -> Option<u64> {
+    let link = fs::read_link(proc.join(pid).join("ns/net")).ok()?;
+    parse_netns_link(&link.to_string_lossy())
+}
+
+fn parse_netns_link(link: &str) -> Option<u64> {
+    link.strip_prefix("net:[")?.strip_suffix(']')?.parse().ok()
+}
+
+/// One representative PID per network namespace, with Porter's own namespace
+/// always present even if no other process could be inspected
+fn distinct_namespaces(proc: &Path) -> BTreeMap<u64, String> {
+    let mut namespaces = BTreeMap::new();
+
+    if let Some(inode) = netns_inode(proc, "self") {
+        namespaces.insert(inode, "self".to_string());
+    }
+
+    let Ok(entries) = fs::read_dir(proc) else {
+        return namespaces;
+    };
+
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if name.parse::<u32>().is_err() {
+            continue;
+        }
+
+        // Reading another user's ns link needs ptrace access; skip those
+        if let Some(inode) = netns_inode(proc, &name) {
+            namespaces.entry(inode).or_insert(name);
+        }
+    }
+
+    namespaces
+}
+
+/// Names created by `ip netns add`, which bind-mounts the namespace under /run/netns
+fn named_namespaces() -> HashMap<u64, String> {
+    let Ok(entries) = fs::read_dir("/run/netns") else {
+        return HashMap::new();
+    };
+
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let inode = fs::metadata(entry.path()).ok()?.ino();
+            Some((inode, entry.file_name().to_string_lossy().into_owned()))
+        })
+        .collect()
+}
+
+/// The PID a process has inside its innermost PID namespace (last `NSpid` entry),
+/// if that differs from the PID Porter sees
+pub fn namespace_pid(pid: u32) -> Option<u32> {
+    parse_nspid(&fs::read_to_string(format!("/proc/{}/status", pid)).ok()?)
+}
+
+fn parse_nspid(status: &str) -> Option<u32> {
+    let pids: Vec<u32> = status
+        .lines()
+        .find_map(|line| line.strip_prefix("NSpid:"))?
+        .split_whitespace()
+        .filter_map(|p| p.parse().ok())
+        .collect();
+
+    match pids.last() {
+        Some(&inner) if pids.len() > 1 => Some(inner),
+        _ => None,
+    }
+}
+
+/// Socket inode to PIDs map, built in a single pass over `/proc/<pid>/fd`
+struct SocketOwners {
+    /// Every process holding the socket, lowest PID first
+    by_inode: HashMap<u64, Vec<u32>>,
+    /// PIDs whose fd table could not be read (EACCES)
+    unreadable: usize,
+    /// Owners of those PIDs, to tell which unowned sockets they may be holding
+    unreadable_uids: HashSet<u32>,
+    hidepid: Option<String>,
+    child_pid_namespace: bool,
+}
+
+impl SocketOwners {
+    fn scan() -> Self {
+        let mut owners = Self {
+            by_inode: HashMap::new(),
+            unreadable: 0,
+            unreadable_uids: HashSet::new(),
+            hidepid: hidepid_option(),
+            child_pid_namespace: in_child_pid_namespace(),
+        };
+
+        let Ok(entries) = fs::read_dir("/proc") else {
+            return owners;
+        };
+
+        for entry in entries.flatten() {
+            let Ok(pid) = entry.file_name().to_string_lossy().parse::<u32>() else {
+                continue;
+            };
+
+            let fd_entries = match fs::read_dir(entry.path().join("fd")) {
+                Ok(fd_entries) => fd_entries,
+                Err(e) if e.kind() == ErrorKind::PermissionDenied => {
+                    owners.unreadable += 1;
+                    if let Ok(metadata) = entry.metadata() {
+                        owners.unreadable_uids.insert(metadata.uid());
+                    }
+                    continue;
+                }
+                Err(_) => continue,
+            };
+
+            for fd_entry in fd_entries.flatten() {
+                if let Ok(link) = fs::read_link(fd_entry.path()) {
+                    if let Some(inode) = parse_socket_link(&link.to_string_lossy()) {
+                        let holders = owners.by_inode.entry(inode).or_default();
+                        // A process may hold the same socket on several descriptors
+                        if holders.last() != Some(&pid) {
+                            holders.push(pid);
+                        }
+                    }
+                }
+            }
+        }
+
+        owners
+    }
+
+    /// The lowest PID holding the socket, or 0 when none could be found
+    fn pid(&self, inode: u64) -> u32 {
+        self.by_inode.get(&inode).and_then(|holders| holders.first()).copied().unwrap_or(0)
+    }
+
+    /// Holders other than the one reported by `pid`
+    fn shared_with(&self, inode: u64) -> Vec<u32> {
+        self.by_inode
+            .get(&inode)
+            .map(|holders| holders.iter().skip(1).copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Why a socket could not be tied to a process, if it could not. `uid` is the
+    /// socket's owner where the table reports one.
+    fn explain(&self, inode: u64, uid: Option<u32>) -> Option<AttributionFailure> {
+        if self.by_inode.contains_key(&inode) {
+            return None;
+        }
+
+        // TIME_WAIT and similar sockets no longer belong to any process
+        if inode == 0 {
+            return Some(AttributionFailure::NoOwner);
+        }
+
+        // Without a UID, any unreadable or hidden process could be the owner
+        let euid = unsafe { libc::geteuid() };
+        let other_user = uid != Some(euid);
+        let unreadable_owner = match uid {
+            Some(uid) => self.unreadable_uids.contains(&uid),
+            None => self.unreadable > 0,
+        };
+
+        let failure = if self.hidepid.is_some() && euid != 0 && other_user {
+            AttributionFailure::Hidepid
+        } else if unreadable_owner {
+            AttributionFailure::PermissionDenied
+        } else if self.child_pid_namespace {
+            AttributionFailure::OtherNamespace
+        } else {
+            AttributionFailure::Unknown
+        };
+
+        Some(failure)
+    }
+}
+
+fn parse_socket_link(link: &str) -> Option<u64> {
+    link.strip_prefix("socket:[")?.strip_suffix(']')?.parse().ok()
+}
+
+fn parse_proc_net_tcp(path: &str, owners: &SocketOwners) -> Result<Vec<NetworkConnection>> {
     let content = fs::read_to_string(path)?;
     let mut connections = Vec::new();
 
@@ -39,25 +476,115 @@ fn parse_proc_net_tcp(path: &str) -> Result<Vec<NetworkConnection>> {
         let local_port = u16::from_str_radix(local_parts[1], 16).unwrap_or(0);
         let local_addr = parse_hex_address(local_parts[0]);
 
+        let (remote_addr, remote_port) = match parts[2].split_once(':') {
+            Some((addr, port)) => (
+                parse_hex_address(addr),
+                u16::from_str_radix(port, 16).unwrap_or(0),
+            ),
+            None => (String::new(), 0),
+        };
+
         // Parse PID from inode
         let inode = parts[9].parse::<u64>().unwrap_or(0);
-        let pid = find_pid_by_inode(inode).unwrap_or(0);
+        let pid = owners.pid(inode);
+
+        let (tx_queue, rx_queue) = parts[4].split_once(':').unwrap_or(("0", "0"));
+        let (timer, expires) = parts[5].split_once(':').unwrap_or(("0", "0"));
+        let tcp = TcpDetails {
+            tx_queue: u32::from_str_radix(tx_queue, 16).unwrap_or(0),
+            rx_queue: u32::from_str_radix(rx_queue, 16).unwrap_or(0),
+            max_backlog: None,
+            timer: match timer {
+                "01" => TcpTimer::Retransmit,
+                "02" => TcpTimer::KeepAlive,
+                "03" => TcpTimer::TimeWait,
+                "04" => TcpTimer::ZeroWindowProbe,
+                _ => TcpTimer::Off,
+            },
+            timer_expires_ms: clock_ticks_to_ms(u64::from_str_radix(expires, 16).unwrap_or(0)),
+            retransmits: u32::from_str_radix(parts[6], 16).unwrap_or(0),
+            uid: parts[7].parse().unwrap_or(0),
+            inode,
+            info: None,
+        };
 
         connections.push(NetworkConnection {
             local_address: local_addr,
             local_port,
-            remote_address: String::new(),
-            remote_port: 0,
+            remote_address: remote_addr,
+            remote_port,
             protocol: Protocol::TCP,
             pid,
-            state: "ESTABLISHED".to_string(),
+            shared_with: owners.shared_with(inode),
+            state: format_tcp_state(parts[3]),
+            unattributed: owners.explain(inode, Some(tcp.uid)),
+            namespace: None,
+            tcp: Some(tcp),
         });
     }
 
     Ok(connections)
 }
 
+/// `ListenOverflows` and `ListenDrops` from the `TcpExt` section of `/proc/net/netstat`
+pub fn listen_counters() -> Option<(u64, u64)> {
+    let content = fs::read_to_string("/proc/net/netstat").ok()?;
+    let mut lines = content.lines().filter(|line| line.starts_with("TcpExt:"));
+    let names = lines.next()?.split_whitespace();
+    let values = lines.next()?.split_whitespace();
+
+    let counters: HashMap<&str, u64> = names
+        .zip(values)
+        .filter_map(|(name, value)| Some((name, value.parse().ok()?)))
+        .collect();
+
+    Some((*counters.get("ListenOverflows")?, *counters.get("ListenDrops")?))
+}
+
+/// `/proc/net/tcp` timer expiry is in USER_HZ clock ticks
+fn clock_ticks_to_ms(ticks: u64) -> u64 {
+    let hz = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+    if hz > 0 {
+        ticks * 1000 / hz as u64
+    } else {
+        ticks * 10
+    }
+}
+
+/// Netlink socket data keyed by inode; empty when netlink is unavailable
+pub fn tcp_diag_by_inode() -> HashMap<u64, TcpDiag> {
+    super::netlink::tcp_diag_by_inode().unwrap_or_default()
+}
+
+fn format_tcp_state(hex: &str) -> String {
+    match u8::from_str_radix(hex, 16).unwrap_or(0) {
+        0x01 => "ESTABLISHED",
+        0x02 => "SYN_SENT",
+        0x03 => "SYN_RCVD",
+        0x04 => "FIN_WAIT1",
+        0x05 => "FIN_WAIT2",
+        0x06 => "TIME_WAIT",
+        0x07 => "CLOSED",
+        0x08 => "CLOSE_WAIT",
+        0x09 => "LAST_ACK",
+        0x0A => "LISTEN",
+        0x0B => "CLOSING",
+        _ => "UNKNOWN",
+    }
+    .to_string()
+}
+
 fn parse_hex_address(hex: &str) -> String {
+    // tcp6 tables print the address as four native-endian 32-bit words
+    if hex.len() == 32 {
+        let mut bytes = [0u8; 16];
+        for (i, chunk) in bytes.chunks_mut(4).enumerate() {
+            let word = u32::from_str_radix(&hex[i * 8..i * 8 + 8], 16).unwrap_or(0);
+            chunk.copy_from_slice(&word.to_ne_bytes());
+        }
+        return std::net::Ipv6Addr::from(bytes).to_string();
+    }
+
     let addr = u32::from_str_radix(hex, 16).unwrap_or(0);
     format!(
         "{}.{}.{}.{}",
@@ -68,32 +595,280 @@ fn parse_hex_address(hex: &str) -> String {
     )
 }
 
-fn find_pid_by_inode(inode: u64) -> Option<u32> {
-    let proc_path = Path::new("/proc");
+pub fn is_system_process(pid: u32) -> bool {
+    // Linux system processes typically have PID < 1000
+    pid < 1000
+}
 
-    if let Ok(entries) = fs::read_dir(proc_path) {
-        for entry in entries.flatten() {
-            if let Ok(pid) = entry.file_name().to_string_lossy().parse::<u32>() {
-                let fd_path = entry.path().join("fd");
-
-                if let Ok(fd_entries) = fs::read_dir(fd_path) {
-                    for fd_entry in fd_entries.flatten() {
-                        if let Ok(link) = fs::read_link(fd_entry.path()) {
-                            let link_str = link.to_string_lossy();
-                            if link_str.contains(&format!("socket:[{}]", inode)) {
-                                return Some(pid);
-                            }
-                        }
-                    }
-                }
-            }
+/// cgroup v2 path of a process, relative to the cgroup mount
+pub fn cgroup_path(pid: u32) -> Option<String> {
+    let content = fs::read_to_string(format!("/proc/{}/cgroup", pid)).ok()?;
+    content
+        .lines()
+        .find_map(|line| line.strip_prefix("0::"))
+        .map(String::from)
+}
+
+/// Freeze or thaw a cgroup v2 by writing its `cgroup.freeze` file
+pub fn set_cgroup_frozen(cgroup: &str, frozen: bool) -> Result<()> {
+    let path = Path::new("/sys/fs/cgroup")
+        .join(cgroup.trim_start_matches('/'))
+        .join("cgroup.freeze");
+    fs::write(path, if frozen { "1" } else { "0" })?;
+    Ok(())
+}
+
+/// Process start time in clock ticks since boot (field 22 of `/proc/<pid>/stat`)
+pub fn process_start_ticks(pid: u32) -> Option<u64> {
+    let stat = fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    // The command name may contain spaces, so count fields after its closing paren
+    let (_, rest) = stat.rsplit_once(')')?;
+    rest.split_whitespace().nth(19)?.parse().ok()
+}
+
+/// The `hidepid=` option /proc is mounted with, if it hides anything
+pub fn hidepid_option() -> Option<String> {
+    let mounts = fs::read_to_string("/proc/self/mounts").ok()?;
+
+    mounts
+        .lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>())
+        .filter(|fields| fields.len() > 3 && fields[1] == "/proc" && fields[2] == "proc")
+        .flat_map(|fields| fields[3].split(',').map(String::from).collect::<Vec<_>>())
+        .find_map(|option| option.strip_prefix("hidepid=").map(String::from))
+        .filter(|mode| mode != "0" && mode != "off")
+}
+
+/// Whether Porter runs inside a nested PID namespace (e.g. a container)
+pub fn in_child_pid_namespace() -> bool {
+    fs::read_to_string("/proc/self/status")
+        .ok()
+        .and_then(|status| {
+            status
+                .lines()
+                .find_map(|line| line.strip_prefix("NSpid:"))
+                .map(|pids| pids.split_whitespace().count() > 1)
+        })
+        .unwrap_or(false)
+}
+
+/// Effective capability set of the current process (`CapEff` in `/proc/self/status`)
+pub fn effective_capabilities() -> Option<u64> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let hex = status.lines().find_map(|line| line.strip_prefix("CapEff:"))?;
+    u64::from_str_radix(hex.trim(), 16).ok()
+}
+
+fn platform_diagnostics(owners: &SocketOwners) -> PlatformDiagnostics {
+    const CAPABILITIES: [(&str, u32); 4] = [
+        ("CAP_DAC_READ_SEARCH", 2),
+        ("CAP_KILL", 5),
+        ("CAP_NET_ADMIN", 12),
+        ("CAP_SYS_PTRACE", 19),
+    ];
+
+    let effective = effective_capabilities().unwrap_or(0);
+
+    PlatformDiagnostics {
+        backend: "procfs",
+        capabilities: CAPABILITIES
+            .iter()
+            .map(|(name, bit)| (name.to_string(), effective & (1 << bit) != 0))
+            .collect(),
+        hidepid: owners.hidepid.clone(),
+        isolated_pid_namespace: owners.child_pid_namespace,
+        unreadable_processes: owners.unreadable,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::symlink;
+
+    /// Owners as a scan of `/proc/<pid>/fd` would have found them
+    fn owners(holders: &[(u64, &[u32])]) -> SocketOwners {
+        SocketOwners {
+            by_inode: holders.iter().map(|(inode, pids)| (*inode, pids.to_vec())).collect(),
+            unreadable: 0,
+            unreadable_uids: HashSet::new(),
+            hidepid: None,
+            child_pid_namespace: false,
         }
     }
 
-    None
-}
+    #[test]
+    fn nspid_reports_the_innermost_pid_only_inside_a_child_namespace() {
+        let cases = [
+            ("Name:\tnginx\nPid:\t812\nNSpid:\t812\nNSpgid:\t812\n", None),
+            ("Name:\tnginx\nPid:\t4211\nNSpid:\t4211\t1\nNSpgid:\t4211\t1\n", Some(1)),
+            ("Name:\tpython3\nPid:\t5003\nNSpid:\t5003\t310\t7\n", Some(7)),
+            // Kernels before 4.1 have no NSpid line
+            ("Name:\tsshd\nPid:\t901\nPPid:\t1\n", None),
+        ];
 
-pub fn is_system_process(pid: u32) -> bool {
-    // Linux system processes typically have PID < 1000
-    pid < 1000
+        for (status, expected) in cases {
+            assert_eq!(parse_nspid(status), expected, "{:?}", status);
+        }
+    }
+
+    #[test]
+    fn netns_links_parse_to_their_inode() {
+        assert_eq!(parse_netns_link("net:[4026531840]"), Some(4026531840));
+        assert_eq!(parse_netns_link("net:[]"), None);
+        assert_eq!(parse_netns_link("mnt:[4026531841]"), None);
+        assert_eq!(parse_netns_link("socket:[4026531840]"), None);
+    }
+
+    #[test]
+    fn one_pid_represents_each_namespace() {
+        let proc = tempfile::tempdir().unwrap();
+        for (pid, link) in [
+            ("self", "net:[4026531840]"),
+            ("1", "net:[4026531840]"),
+            ("4211", "net:[4026532301]"),
+            ("4212", "net:[4026532301]"),
+            ("5003", "net:[4026532517]"),
+        ] {
+            let ns = proc.path().join(pid).join("ns");
+            fs::create_dir_all(&ns).unwrap();
+            symlink(link, ns.join("net")).unwrap();
+        }
+        // Not a process, and a process whose ns link could not be read
+        fs::create_dir_all(proc.path().join("sys/ns")).unwrap();
+        fs::create_dir_all(proc.path().join("6000")).unwrap();
+
+        let namespaces = distinct_namespaces(proc.path());
+
+        assert_eq!(namespaces.len(), 3);
+        assert_eq!(namespaces[&4026531840], "self");
+        assert!(["4211", "4212"].contains(&namespaces[&4026532301].as_str()));
+        assert_eq!(namespaces[&4026532517], "5003");
+        assert_eq!(netns_inode(proc.path(), "self"), Some(4026531840));
+        assert_eq!(netns_inode(proc.path(), "6000"), None);
+    }
+
+    #[test]
+    fn unix_sockets_parse_type_state_path_and_owners() {
+        let content = "\
+Num       RefCount Protocol Flags    Type St Inode Path
+00000000a95845ab: 00000002 00000000 00010000 0001 01 76183 /run/postgresql/.s.PGSQL.5432
+0000000020f4f8e4: 00000003 00000000 00000000 0001 03 79165 /run/postgresql/.s.PGSQL.5432
+0000000085c763d7: 00000003 00000000 00000000 0001 03 79164
+00000000eeaa0016: 00000002 00000000 00000000 0002 01 18421 /run/systemd/journal/dev-log
+000000008f12245e: 00000002 00000000 00010000 0005 01 20117 @/tmp/.X11-unix/X0
+0000000057f28762: 00000002 00000000 00010000 0001 01 31337 /home/dev/My Sockets/app.sock
+00000000ef6565d2: 00000002 00000000 00000000 0003 01 40001 /tmp/unknown-type
+";
+        let owners = owners(&[(76183, &[900, 901, 902]), (79165, &[901]), (18421, &[1])]);
+        let sockets = parse_proc_net_unix(content, &owners);
+
+        let summary: Vec<_> = sockets
+            .iter()
+            .map(|s| (s.inode, s.socket_type, s.state.as_str(), s.listening, s.path.as_deref(), s.pid))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (76183, UnixSocketType::Stream, "LISTEN", true, Some("/run/postgresql/.s.PGSQL.5432"), 900),
+                (79165, UnixSocketType::Stream, "CONNECTED", false, Some("/run/postgresql/.s.PGSQL.5432"), 901),
+                (79164, UnixSocketType::Stream, "CONNECTED", false, None, 0),
+                (18421, UnixSocketType::Dgram, "UNCONNECTED", false, Some("/run/systemd/journal/dev-log"), 1),
+                (20117, UnixSocketType::Seqpacket, "LISTEN", true, Some("@/tmp/.X11-unix/X0"), 0),
+                (31337, UnixSocketType::Stream, "LISTEN", true, Some("/home/dev/My Sockets/app.sock"), 0),
+            ]
+        );
+        assert_eq!(sockets[0].shared_with, [901, 902]);
+        assert!(sockets[1].shared_with.is_empty());
+    }
+
+    /// `(protocol, local address, port, IP protocol, inode, pid)`
+    type Summary<'a> = (Protocol, &'a str, Option<u16>, Option<u8>, u64, u32);
+
+    fn summarize(sockets: &[OtherSocketEntry]) -> Vec<Summary<'_>> {
+        sockets
+            .iter()
+            .map(|s| (s.protocol, s.local_address.as_str(), s.port, s.ip_protocol, s.inode, s.pid))
+            .collect()
+    }
+
+    #[test]
+    fn raw_sockets_report_their_ip_protocol_and_icmp_sockets_their_id() {
+        let raw = "\
+  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode ref pointer drops
+   1: 00000000:0001 00000000:0000 07 00000000:00000000 00:00000000 00000000     0        0 51234 2 0000000000000000 0
+  58: 0100007F:00FF 00000000:0000 07 00000000:00000000 00:00000000 00000000     0        0 51235 2 0000000000000000 0
+";
+        let raw6 = "\
+  sl  local_address                         remote_address                        st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode ref pointer drops
+  58: 00000000000000000000000000000000:003A 00000000000000000000000000000000:0000 07 00000000:00000000 00:00000000 00000000     0        0 51236 2 0000000000000000 0
+";
+        let icmp = "\
+  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode ref pointer drops
+ 142: 00000000:1F2E 00000000:0000 07 00000000:00000000 00:00000000 00000000  1000        0 61002 2 0000000000000000 0
+   3: truncated line
+";
+        let owners = owners(&[(51234, &[700]), (61002, &[1500])]);
+
+        assert_eq!(
+            summarize(&parse_proc_net_inet(raw, Protocol::Raw, &owners)),
+            [
+                (Protocol::Raw, "0.0.0.0", None, Some(1), 51234, 700),
+                (Protocol::Raw, "127.0.0.1", None, Some(255), 51235, 0),
+            ]
+        );
+        assert_eq!(
+            summarize(&parse_proc_net_inet(raw6, Protocol::Raw, &owners)),
+            [(Protocol::Raw, "::", None, Some(58), 51236, 0)]
+        );
+        assert_eq!(
+            summarize(&parse_proc_net_inet(icmp, Protocol::Icmp, &owners)),
+            [(Protocol::Icmp, "0.0.0.0", Some(7982), None, 61002, 1500)]
+        );
+    }
+
+    #[test]
+    fn packet_sockets_report_ethertype_and_bound_interface() {
+        let content = "\
+sk               RefCnt Type Proto  Iface R Rmem   User   Inode
+ffff8f0c4a2d6000 3      3    0003   0     1 0      0      24117
+ffff8f0c4a2d7800 3      2    0800   2     1 0      0      24118
+ffff8f0c4a2d8000 3      3    88cc   7     1 0      101    24119
+";
+        let interfaces = HashMap::from([(2, "eth0".to_string())]);
+        let owners = owners(&[(24117, &[810]), (24118, &[1333])]);
+        let sockets = parse_proc_net_packet(content, &interfaces, &owners);
+
+        let summary: Vec<_> = sockets
+            .iter()
+            .map(|s| (s.ethertype, s.interface.as_deref(), s.inode, s.pid))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (Some(0x0003), None, 24117, 810),
+                (Some(0x0800), Some("eth0"), 24118, 1333),
+                (Some(0x88cc), Some("if7"), 24119, 0),
+            ]
+        );
+        assert!(sockets.iter().all(|s| s.protocol == Protocol::Packet && s.port.is_none()));
+    }
+
+    #[test]
+    fn sctp_endpoints_list_every_bound_address() {
+        let content = "\
+ ENDPT     SOCK   STY SST HBKT LPORT   UID INODE LADDRS
+ffff9a2b41e3c000 ffff9a2b4ad6a000 2   10  29   36412     0 88213 10.0.0.5 192.168.1.5
+ffff9a2b41e3d000 ffff9a2b4ad6b000 1   10  3    2905   1000 88214 0.0.0.0
+";
+        let owners = owners(&[(88213, &[4100])]);
+
+        assert_eq!(
+            summarize(&parse_proc_net_sctp(content, &owners)),
+            [
+                (Protocol::Sctp, "10.0.0.5 192.168.1.5", Some(36412), None, 88213, 4100),
+                (Protocol::Sctp, "0.0.0.0", Some(2905), None, 88214, 0),
+            ]
+        );
+    }
 }
@@ -1,5 +1,9 @@
-use super::{NetworkConnection, Protocol};
+use super::{
+    NetworkConnection, OtherSocketEntry, PlatformDiagnostics, Protocol, TcpDiag, UnixSocketEntry,
+};
+use crate::error::PorterError;
 use anyhow::Result;
+use std::collections::HashMap;
 use std::process::Command;
 
 pub fn get_network_connections() -> Result<Vec<NetworkConnection>> {
@@ -39,7 +43,11 @@ pub fn get_network_connections() -> Result<Vec<NetworkConnection>> {
                     remote_port: 0,
                     protocol,
                     pid,
+                    shared_with: Vec::new(),
                     state: parts.get(9).unwrap_or(&"").to_string(),
+                    unattributed: None,
+                    namespace: None,
+                    tcp: None,
                 });
             }
         }
@@ -52,3 +60,36 @@ pub fn is_system_process(pid: u32) -> bool {
     // macOS system processes typically have PID < 500
     pid < 500
 }
+
+/// There is no system-wide table of Unix domain sockets to read here
+pub fn get_unix_sockets() -> Result<Vec<UnixSocketEntry>> {
+    Err(PorterError::unsupported("listing Unix domain sockets").into())
+}
+
+pub fn tcp_diag_by_inode() -> HashMap<u64, TcpDiag> {
+    HashMap::new()
+}
+
+pub fn listen_counters() -> Option<(u64, u64)> {
+    None
+}
+
+pub fn get_other_sockets() -> Result<Vec<OtherSocketEntry>> {
+    Err(PorterError::unsupported("listing raw, packet and SCTP sockets").into())
+}
+
+/// macOS has no network namespaces, so this is the regular scan
+pub fn get_namespaced_connections() -> Result<Vec<NetworkConnection>> {
+    get_network_connections()
+}
+
+pub fn namespace_pid(_pid: u32) -> Option<u32> {
+    None
+}
+
+pub fn platform_diagnostics() -> PlatformDiagnostics {
+    PlatformDiagnostics {
+        backend: "lsof",
+        ..Default::default()
+    }
+}
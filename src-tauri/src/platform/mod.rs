@@ -12,8 +12,10 @@ pub use macos::*;
 mod linux;
 #[cfg(target_os = "linux")]
 pub use linux::*;
+#[cfg(target_os = "linux")]
+mod netlink;
 
-use crate::models::Protocol;
+use crate::models::{AttributionFailure, NetNamespace, Protocol, TcpDetails, TcpInfo, UnixSocketType};
 
 #[derive(Debug, Clone)]
 pub struct NetworkConnection {
@@ -23,5 +25,71 @@ pub struct NetworkConnection {
     pub remote_port: u16,
     pub protocol: Protocol,
     pub pid: u32,
+    /// Other processes holding the same socket, e.g. workers forked after `bind`.
+    /// Backends that list each holder as its own entry leave this empty.
+    pub shared_with: Vec<u32>,
+    pub state: String,
+    /// Set when the socket exists but its owning process could not be determined
+    pub unattributed: Option<AttributionFailure>,
+    /// Only set by namespace-aware scans
+    pub namespace: Option<NetNamespace>,
+    /// Queue and timer state, where the backend exposes it
+    pub tcp: Option<TcpDetails>,
+}
+
+#[derive(Debug, Clone)]
+pub struct UnixSocketEntry {
+    pub path: Option<String>,
+    pub socket_type: UnixSocketType,
     pub state: String,
+    pub listening: bool,
+    pub inode: u64,
+    pub pid: u32,
+    /// Other processes holding the same socket, as for [`NetworkConnection`]
+    pub shared_with: Vec<u32>,
+}
+
+#[derive(Debug, Clone)]
+pub struct OtherSocketEntry {
+    pub protocol: Protocol,
+    pub local_address: String,
+    pub port: Option<u16>,
+    pub ip_protocol: Option<u8>,
+    pub ethertype: Option<u16>,
+    pub interface: Option<String>,
+    pub inode: u64,
+    pub pid: u32,
+}
+
+/// Per-socket data only the netlink `sock_diag` backend provides
+#[derive(Debug, Clone, Default)]
+pub struct TcpDiag {
+    pub info: Option<TcpInfo>,
+    /// Set for listening sockets
+    pub max_backlog: Option<u32>,
+    /// `IPV6_V6ONLY`, set for IPv6 sockets
+    pub v6_only: Option<bool>,
+}
+
+/// Backend and privilege details reported by the `diagnostics` command
+#[derive(Debug, Clone, Default)]
+pub struct PlatformDiagnostics {
+    pub backend: &'static str,
+    /// Capability name and whether it is in the effective set
+    pub capabilities: Vec<(String, bool)>,
+    pub hidepid: Option<String>,
+    pub isolated_pid_namespace: bool,
+    pub unreadable_processes: usize,
+}
+
+/// Connections and diagnostics together; the Linux backend gets both from one scan
+#[cfg(not(target_os = "linux"))]
+pub fn get_network_connections_with_diagnostics() -> anyhow::Result<(Vec<NetworkConnection>, PlatformDiagnostics)> {
+    Ok((get_network_connections()?, platform_diagnostics()))
+}
+
+impl NetworkConnection {
+    pub fn is_listening(&self) -> bool {
+        self.state.contains("LISTEN")
+    }
 }
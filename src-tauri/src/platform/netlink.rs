@@ -0,0 +1,219 @@
+use super::TcpDiag;
+use crate::models::TcpInfo;
+use std::collections::HashMap;
+use std::io;
+use std::mem::size_of;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+
+const NETLINK_SOCK_DIAG: libc::c_int = 4;
+const SOCK_DIAG_BY_FAMILY: u16 = 20;
+const INET_DIAG_INFO: u16 = 2;
+const INET_DIAG_SKV6ONLY: u16 = 11;
+
+const NLMSG_HEADER_LEN: usize = 16;
+/// `struct inet_diag_msg`: family, state, timer, retrans, sockid (48), expires, rqueue, wqueue, uid, inode
+const INET_DIAG_MSG_LEN: usize = 72;
+const INET_DIAG_MSG_STATE: usize = 1;
+const INET_DIAG_MSG_WQUEUE: usize = 60;
+const INET_DIAG_MSG_INODE: usize = 68;
+const TCP_LISTEN: u8 = 10;
+
+#[repr(C)]
+struct InetDiagSockId {
+    sport: u16,
+    dport: u16,
+    src: [u32; 4],
+    dst: [u32; 4],
+    interface: u32,
+    cookie: [u32; 2],
+}
+
+#[repr(C)]
+struct InetDiagReqV2 {
+    family: u8,
+    protocol: u8,
+    ext: u8,
+    pad: u8,
+    states: u32,
+    id: InetDiagSockId,
+}
+
+#[repr(C)]
+struct Request {
+    header: libc::nlmsghdr,
+    body: InetDiagReqV2,
+}
+
+/// `tcp_info`, listen backlog and `IPV6_V6ONLY` of every TCP socket in Porter's network namespace,
+/// keyed by inode, via a `NETLINK_SOCK_DIAG` dump; `/proc/net/tcp` has neither
+pub fn tcp_diag_by_inode() -> io::Result<HashMap<u64, TcpDiag>> {
+    let fd = unsafe {
+        libc::socket(
+            libc::AF_NETLINK,
+            libc::SOCK_DGRAM | libc::SOCK_CLOEXEC,
+            NETLINK_SOCK_DIAG,
+        )
+    };
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let socket = unsafe { OwnedFd::from_raw_fd(fd) };
+
+    // Never let a misbehaving kernel reply stall a scan
+    let timeout = libc::timeval { tv_sec: 1, tv_usec: 0 };
+    unsafe {
+        libc::setsockopt(
+            socket.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_RCVTIMEO,
+            &timeout as *const _ as *const libc::c_void,
+            size_of::<libc::timeval>() as libc::socklen_t,
+        );
+    }
+
+    let mut diags = HashMap::new();
+    for family in [libc::AF_INET, libc::AF_INET6] {
+        dump_family(&socket, family as u8, &mut diags)?;
+    }
+
+    Ok(diags)
+}
+
+fn dump_family(socket: &OwnedFd, family: u8, diags: &mut HashMap<u64, TcpDiag>) -> io::Result<()> {
+    let request = Request {
+        header: libc::nlmsghdr {
+            nlmsg_len: size_of::<Request>() as u32,
+            nlmsg_type: SOCK_DIAG_BY_FAMILY,
+            nlmsg_flags: (libc::NLM_F_REQUEST | libc::NLM_F_DUMP) as u16,
+            nlmsg_seq: 1,
+            nlmsg_pid: 0,
+        },
+        body: InetDiagReqV2 {
+            family,
+            protocol: libc::IPPROTO_TCP as u8,
+            ext: 1 << (INET_DIAG_INFO - 1),
+            pad: 0,
+            states: !0,
+            id: InetDiagSockId {
+                sport: 0,
+                dport: 0,
+                src: [0; 4],
+                dst: [0; 4],
+                interface: 0,
+                cookie: [0; 2],
+            },
+        },
+    };
+
+    let sent = unsafe {
+        libc::send(
+            socket.as_raw_fd(),
+            &request as *const _ as *const libc::c_void,
+            size_of::<Request>(),
+            0,
+        )
+    };
+    if sent < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    let mut buffer = vec![0u8; 32 * 1024];
+    loop {
+        let received = unsafe {
+            libc::recv(
+                socket.as_raw_fd(),
+                buffer.as_mut_ptr() as *mut libc::c_void,
+                buffer.len(),
+                0,
+            )
+        };
+        if received < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let mut data = &buffer[..received as usize];
+        while data.len() >= NLMSG_HEADER_LEN {
+            let length = u32_at(data, 0).unwrap_or(0) as usize;
+            let kind = u16::from_ne_bytes([data[4], data[5]]);
+            if length < NLMSG_HEADER_LEN || length > data.len() {
+                return Ok(());
+            }
+
+            match kind as libc::c_int {
+                libc::NLMSG_DONE => return Ok(()),
+                libc::NLMSG_ERROR => {
+                    let code = u32_at(data, NLMSG_HEADER_LEN).unwrap_or(0) as i32;
+                    return Err(io::Error::from_raw_os_error(-code));
+                }
+                _ => parse_diag_msg(&data[NLMSG_HEADER_LEN..length], diags),
+            }
+
+            data = &data[align(length).min(data.len())..];
+        }
+    }
+}
+
+fn parse_diag_msg(message: &[u8], diags: &mut HashMap<u64, TcpDiag>) {
+    let Some(inode) = u32_at(message, INET_DIAG_MSG_INODE) else {
+        return;
+    };
+
+    // For listeners the kernel reports the backlog limit in place of the send queue
+    let diag = diags.entry(inode as u64).or_default();
+    if message.get(INET_DIAG_MSG_STATE) == Some(&TCP_LISTEN) {
+        diag.max_backlog = u32_at(message, INET_DIAG_MSG_WQUEUE);
+    }
+
+    // Route attributes follow the fixed-size message
+    let mut attributes = message.get(INET_DIAG_MSG_LEN..).unwrap_or_default();
+    while attributes.len() >= 4 {
+        let length = u16::from_ne_bytes([attributes[0], attributes[1]]) as usize;
+        let kind = u16::from_ne_bytes([attributes[2], attributes[3]]);
+        if length < 4 || length > attributes.len() {
+            return;
+        }
+
+        match kind {
+            INET_DIAG_INFO => diag.info = Some(parse_tcp_info(&attributes[4..length])),
+            // Sent for every AF_INET6 socket
+            INET_DIAG_SKV6ONLY => diag.v6_only = attributes.get(4).map(|&flag| flag != 0),
+            _ => {}
+        }
+
+        attributes = &attributes[align(length).min(attributes.len())..];
+    }
+}
+
+/// Offsets into `struct tcp_info`; older kernels send a shorter struct,
+/// so missing trailing fields read as zero
+fn parse_tcp_info(info: &[u8]) -> TcpInfo {
+    let field = |offset| u32_at(info, offset).unwrap_or(0);
+    let wide = |offset| u64_at(info, offset).unwrap_or(0);
+
+    TcpInfo {
+        rto_us: field(8),
+        snd_mss: field(16),
+        unacked: field(24),
+        lost: field(32),
+        last_data_sent_ms: field(44),
+        last_data_recv_ms: field(52),
+        rtt_us: field(68),
+        rtt_var_us: field(72),
+        snd_cwnd: field(80),
+        total_retrans: field(100),
+        bytes_acked: wide(120),
+        bytes_received: wide(128),
+    }
+}
+
+fn align(length: usize) -> usize {
+    (length + 3) & !3
+}
+
+fn u32_at(data: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_ne_bytes(data.get(offset..offset + 4)?.try_into().ok()?))
+}
+
+fn u64_at(data: &[u8], offset: usize) -> Option<u64> {
+    Some(u64::from_ne_bytes(data.get(offset..offset + 8)?.try_into().ok()?))
+}
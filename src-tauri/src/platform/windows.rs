@@ -1,5 +1,9 @@
-use super::{NetworkConnection, Protocol};
+use super::{
+    NetworkConnection, OtherSocketEntry, PlatformDiagnostics, Protocol, TcpDiag, UnixSocketEntry,
+};
+use crate::error::PorterError;
 use anyhow::Result;
+use std::collections::HashMap;
 
 #[cfg(target_os = "windows")]
 use windows::Win32::NetworkManagement::IpHelper::*;
@@ -66,7 +70,11 @@ pub fn get_network_connections() -> Result<Vec<NetworkConnection>> {
                     remote_port: 0,
                     protocol: Protocol::TCP,
                     pid: entry.dwOwningPid,
+                    shared_with: Vec::new(),
                     state: format_tcp_state(entry.dwState),
+                    unattributed: None,
+                    namespace: None,
+                    tcp: None,
                 });
             }
         }
@@ -101,6 +109,14 @@ pub fn is_system_process(pid: u32) -> bool {
     pid < 1000
 }
 
+#[cfg(target_os = "windows")]
+pub fn platform_diagnostics() -> PlatformDiagnostics {
+    PlatformDiagnostics {
+        backend: "iphlpapi",
+        ..Default::default()
+    }
+}
+
 #[cfg(not(target_os = "windows"))]
 pub fn get_network_connections() -> Result<Vec<NetworkConnection>> {
     Ok(Vec::new())
@@ -110,3 +126,34 @@ pub fn get_network_connections() -> Result<Vec<NetworkConnection>> {
 pub fn is_system_process(_pid: u32) -> bool {
     false
 }
+
+#[cfg(not(target_os = "windows"))]
+pub fn platform_diagnostics() -> PlatformDiagnostics {
+    PlatformDiagnostics::default()
+}
+
+/// There is no system-wide table of Unix domain sockets to read here
+pub fn get_unix_sockets() -> Result<Vec<UnixSocketEntry>> {
+    Err(PorterError::unsupported("listing Unix domain sockets").into())
+}
+
+pub fn tcp_diag_by_inode() -> HashMap<u64, TcpDiag> {
+    HashMap::new()
+}
+
+pub fn listen_counters() -> Option<(u64, u64)> {
+    None
+}
+
+pub fn get_other_sockets() -> Result<Vec<OtherSocketEntry>> {
+    Err(PorterError::unsupported("listing raw, packet and SCTP sockets").into())
+}
+
+/// Windows has no network namespaces, so this is the regular scan
+pub fn get_namespaced_connections() -> Result<Vec<NetworkConnection>> {
+    get_network_connections()
+}
+
+pub fn namespace_pid(_pid: u32) -> Option<u32> {
+    None
+}
@@ -1,4 +1,6 @@
+use crate::error::PorterError;
 use anyhow::Result;
+use std::path::PathBuf;
 
 /// Check if the current process is running with elevated privileges
 pub fn is_elevated() -> bool {
@@ -63,6 +65,71 @@ pub fn request_elevation() -> Result<()> {
 
     #[cfg(not(target_os = "windows"))]
     {
-        anyhow::bail!("Please restart Porter with sudo/root privileges to kill system processes.");
+        Err(PorterError::ElevationRequired.into())
+    }
+}
+
+/// Where packages install the privileged helper. Never searched for elsewhere:
+/// pkexec would run whatever binary it is given under Porter's polkit action.
+pub const HELPER_PATH: &str = "/usr/lib/porter/porter-helper";
+
+/// The installed privileged helper used through pkexec on Linux
+pub fn helper_path() -> Option<PathBuf> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+
+    Some(PathBuf::from(HELPER_PATH)).filter(|path| path.is_file())
+}
+
+/// Signal a process owned by another user through the pkexec helper (Linux only).
+/// `signal` is one of TERM, KILL, STOP or CONT. The helper checks that the process
+/// still holds `port` and refuses root-owned and system processes.
+pub fn privileged_signal(pid: u32, signal: &str, port: u16) -> Result<()> {
+    #[cfg(target_os = "linux")]
+    {
+        use std::process::Command;
+
+        let helper = helper_path().ok_or_else(|| {
+            PorterError::backend_unavailable("porter-helper", "not installed; reinstall Porter or run it with sudo")
+        })?;
+        let start_ticks = crate::platform::process_start_ticks(pid)
+            .ok_or(PorterError::ProcessNotFound { pid })?;
+
+        let mut command = Command::new("pkexec");
+        command
+            .arg(helper)
+            .arg("signal")
+            .arg(pid.to_string())
+            .arg(start_ticks.to_string())
+            .arg(signal)
+            .arg(port.to_string());
+
+        let output = command
+            .output()
+            .map_err(|e| PorterError::backend_unavailable("pkexec", e))?;
+
+        match output.status.code() {
+            Some(0) => Ok(()),
+            // pkexec reports a dismissed or failed authentication as 126/127
+            Some(126) | Some(127) => Err(PorterError::AuthenticationCancelled.into()),
+            // The helper re-verified the target and refused it
+            Some(3) => Err(PorterError::Protected {
+                pid,
+                reason: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            }
+            .into()),
+            Some(4) => Err(PorterError::permission_denied(pid, "signal").into()),
+            _ => anyhow::bail!(
+                "Privileged helper failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = (pid, signal, port);
+        Err(PorterError::unsupported("The privileged helper").into())
     }
 }
@@ -0,0 +1,111 @@
+use crate::models::AuditEntry;
+use super::storage;
+use anyhow::Result;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+const MAX_FILE_BYTES: u64 = 1024 * 1024;
+const MAX_ROTATED_FILES: usize = 5;
+
+/// Append-only JSON-lines log of destructive actions, rotated as `audit.log.1` .. `audit.log.5`.
+/// The file lives in the user's data directory; on Unix every entry is also sent to the
+/// system log (`authpriv`), so administrators of a shared host see all users' actions in one place.
+pub struct AuditLog {
+    path: PathBuf,
+}
+
+impl AuditLog {
+    pub fn new() -> Self {
+        Self::with_path(storage::data_dir().join("audit.log"))
+    }
+
+    pub fn with_path(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Fails when entries could not be recorded, so callers can refuse to act unaudited
+    pub fn check_writable(&self) -> Result<()> {
+        self.open().map(|_| ())
+    }
+
+    pub fn record(&self, entry: &AuditEntry) -> Result<()> {
+        let line = serde_json::to_string(entry)?;
+        send_to_system_log(&line);
+
+        if fs::metadata(&self.path).map(|m| m.len() >= MAX_FILE_BYTES).unwrap_or(false) {
+            self.rotate()?;
+        }
+
+        writeln!(self.open()?, "{}", line)?;
+        Ok(())
+    }
+
+    fn open(&self) -> Result<fs::File> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        Ok(OpenOptions::new().create(true).append(true).open(&self.path)?)
+    }
+
+    /// Entries from the current and rotated files, newest first
+    pub fn entries(&self, limit: Option<usize>) -> Result<Vec<AuditEntry>> {
+        let mut entries = Vec::new();
+
+        for path in self.files() {
+            let Ok(content) = fs::read_to_string(&path) else {
+                continue;
+            };
+            for line in content.lines().rev() {
+                // Skip lines that were cut off mid-write
+                if let Ok(entry) = serde_json::from_str::<AuditEntry>(line) {
+                    entries.push(entry);
+                }
+                if limit.is_some_and(|limit| entries.len() >= limit) {
+                    return Ok(entries);
+                }
+            }
+        }
+
+        Ok(entries)
+    }
+
+    /// The current file followed by rotated files, newest first
+    fn files(&self) -> Vec<PathBuf> {
+        std::iter::once(self.path.clone())
+            .chain((1..=MAX_ROTATED_FILES).map(|n| self.rotated(n)))
+            .collect()
+    }
+
+    fn rotated(&self, n: usize) -> PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(format!(".{}", n));
+        PathBuf::from(name)
+    }
+
+    fn rotate(&self) -> Result<()> {
+        let _ = fs::remove_file(self.rotated(MAX_ROTATED_FILES));
+        for n in (1..MAX_ROTATED_FILES).rev() {
+            let from = self.rotated(n);
+            if from.exists() {
+                fs::rename(&from, self.rotated(n + 1))?;
+            }
+        }
+        fs::rename(&self.path, self.rotated(1))?;
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+fn send_to_system_log(line: &str) {
+    let Ok(message) = std::ffi::CString::new(format!("porter audit: {}", line)) else {
+        return;
+    };
+    // Pass the entry as an argument so `%` in command lines is not read as a format
+    unsafe {
+        libc::syslog(libc::LOG_AUTHPRIV | libc::LOG_NOTICE, c"%s".as_ptr(), message.as_ptr());
+    }
+}
+
+#[cfg(not(unix))]
+fn send_to_system_log(_line: &str) {}
@@ -0,0 +1,461 @@
+use crate::error::PorterError;
+use crate::models::{ContainerInfo, Protocol, PublishedPort};
+use anyhow::{Result, anyhow};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Listing and inspecting answer at once
+const API_TIMEOUT: Duration = Duration::from_secs(2);
+/// Seconds a container gets to exit after SIGTERM before the runtime kills it
+const STOP_GRACE_SECS: u64 = 10;
+/// A stop answers only once the container has exited, so wait out the grace period and then some
+const STOP_TIMEOUT: Duration = Duration::from_secs(STOP_GRACE_SECS + 5);
+
+/// A Docker-compatible engine API reachable over a local Unix socket.
+/// Podman serves the same API, so both runtimes share this client.
+pub struct ContainerRuntime {
+    name: String,
+    socket: PathBuf,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ApiContainer {
+    id: String,
+    #[serde(default)]
+    names: Vec<String>,
+    #[serde(default)]
+    image: String,
+    #[serde(default)]
+    ports: Vec<ApiPort>,
+    #[serde(default)]
+    labels: Option<HashMap<String, String>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ApiPort {
+    #[serde(rename = "IP", default)]
+    ip: Option<String>,
+    private_port: u16,
+    #[serde(default)]
+    public_port: Option<u16>,
+    #[serde(rename = "Type", default)]
+    kind: String,
+}
+
+impl ContainerRuntime {
+    pub fn new(name: &str, socket: PathBuf) -> Self {
+        Self {
+            name: name.to_string(),
+            socket,
+        }
+    }
+
+    /// Runtimes whose API socket exists on this machine
+    pub fn discover() -> Vec<Self> {
+        let mut candidates = Vec::new();
+
+        if let Some(path) = std::env::var("DOCKER_HOST")
+            .ok()
+            .and_then(|host| host.strip_prefix("unix://").map(PathBuf::from))
+        {
+            candidates.push(Self::new("docker", path));
+        }
+        candidates.push(Self::new("docker", PathBuf::from("/var/run/docker.sock")));
+        if let Some(home) = std::env::var_os("HOME") {
+            // Docker Desktop on macOS
+            candidates.push(Self::new("docker", PathBuf::from(home).join(".docker/run/docker.sock")));
+        }
+        if let Some(runtime_dir) = std::env::var_os("XDG_RUNTIME_DIR") {
+            candidates.push(Self::new("podman", PathBuf::from(runtime_dir).join("podman/podman.sock")));
+        }
+        candidates.push(Self::new("podman", PathBuf::from("/run/podman/podman.sock")));
+
+        let mut seen = Vec::new();
+        candidates
+            .into_iter()
+            .filter(|runtime| {
+                let canonical = runtime.socket.canonicalize().ok();
+                let keep = canonical.is_some() && !seen.contains(&canonical);
+                seen.push(canonical);
+                keep
+            })
+            .collect()
+    }
+
+    /// Every host port published by a running container
+    pub fn published_ports(&self) -> Result<Vec<PublishedPort>> {
+        let (status, body) = self.request("GET", "/containers/json", API_TIMEOUT)?;
+        if status != 200 {
+            return Err(anyhow!("{} API returned HTTP {}: {}", self.name, status, body.trim()));
+        }
+
+        let containers: Vec<ApiContainer> = serde_json::from_str(&body)?;
+        let mut published = Vec::new();
+
+        for container in containers {
+            let labels = container.labels.unwrap_or_default();
+            let info = ContainerInfo {
+                runtime: self.name.clone(),
+                name: container
+                    .names
+                    .first()
+                    .map(|name| name.trim_start_matches('/').to_string())
+                    .unwrap_or_else(|| container.id.chars().take(12).collect()),
+                id: container.id,
+                image: container.image,
+                compose_project: labels.get("com.docker.compose.project").cloned(),
+                compose_service: labels.get("com.docker.compose.service").cloned(),
+                container_port: 0,
+            };
+
+            for port in container.ports {
+                let Some(host_port) = port.public_port else {
+                    continue;
+                };
+                published.push(PublishedPort {
+                    host_ip: port.ip.unwrap_or_else(|| "0.0.0.0".to_string()),
+                    host_port,
+                    protocol: if port.kind == "udp" { Protocol::UDP } else { Protocol::TCP },
+                    container: ContainerInfo {
+                        container_port: port.private_port,
+                        ..info.clone()
+                    },
+                });
+            }
+        }
+
+        Ok(published)
+    }
+
+    /// Stop a container, returning `false` if this runtime does not know it
+    pub fn stop_container(&self, id: &str) -> Result<bool> {
+        validate_id(id)?;
+        let path = format!("/containers/{}/stop?t={}", id, STOP_GRACE_SECS);
+        let (status, body) = self.request("POST", &path, STOP_TIMEOUT)?;
+
+        match status {
+            // 304 means the container was already stopped
+            204 | 304 => Ok(true),
+            404 => Ok(false),
+            _ => Err(anyhow!("{} API returned HTTP {}: {}", self.name, status, body.trim())),
+        }
+    }
+
+    #[cfg(unix)]
+    fn request(&self, method: &str, path: &str, timeout: Duration) -> Result<(u16, String)> {
+        use std::io::{ErrorKind, Read, Write};
+        use std::os::unix::net::UnixStream;
+
+        let mut stream = UnixStream::connect(&self.socket).map_err(|e| {
+            let reason = if e.kind() == ErrorKind::PermissionDenied {
+                format!("permission denied on {}", self.socket.display())
+            } else {
+                e.to_string()
+            };
+            PorterError::backend_unavailable(&self.name, reason)
+        })?;
+        stream.set_read_timeout(Some(timeout))?;
+        stream.set_write_timeout(Some(API_TIMEOUT))?;
+
+        write!(
+            stream,
+            "{} {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\nContent-Length: 0\r\n\r\n",
+            method, path
+        )?;
+
+        let mut raw = Vec::new();
+        stream.read_to_end(&mut raw)?;
+        parse_response(&raw)
+    }
+
+    #[cfg(not(unix))]
+    fn request(&self, _method: &str, _path: &str, _timeout: Duration) -> Result<(u16, String)> {
+        Err(PorterError::unsupported("Container runtime integration").into())
+    }
+}
+
+/// Published ports across every runtime found; runtimes that fail to answer are skipped
+pub fn published_ports() -> Vec<PublishedPort> {
+    ContainerRuntime::discover()
+        .iter()
+        .filter_map(|runtime| runtime.published_ports().ok())
+        .flatten()
+        .collect()
+}
+
+/// Stop a container by ID on whichever runtime owns it. A runtime that fails
+/// (e.g. Docker not running) doesn't stop the others from being asked.
+pub fn stop_container(id: &str) -> Result<()> {
+    validate_id(id)?;
+
+    let mut errors = Vec::new();
+    for runtime in ContainerRuntime::discover() {
+        match runtime.stop_container(id) {
+            Ok(true) => return Ok(()),
+            Ok(false) => {}
+            Err(e) => errors.push(format!("{}: {}", runtime.name, e)),
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(PorterError::backend_unavailable("Container runtime", errors.join("; ")).into());
+    }
+    Err(PorterError::InvalidInput {
+        detail: format!("No such container: {}", id),
+    }
+    .into())
+}
+
+/// The publish a host socket serves, matched on protocol, address and port
+pub fn published_for<'a>(
+    published: &'a [PublishedPort],
+    protocol: Protocol,
+    address: &str,
+    port: u16,
+) -> Option<&'a PublishedPort> {
+    let address = parse_ip(address);
+    published.iter().find(|published| {
+        published.host_port == port
+            && published.protocol == protocol
+            && address.is_some()
+            && parse_ip(&published.host_ip) == address
+    })
+}
+
+fn parse_ip(address: &str) -> Option<IpAddr> {
+    let ip: IpAddr = address.trim_matches(['[', ']']).parse().ok()?;
+    Some(match ip {
+        IpAddr::V6(v6) => v6.to_ipv4_mapped().map(IpAddr::V4).unwrap_or(ip),
+        v4 => v4,
+    })
+}
+
+/// Container IDs and names as Docker accepts them; anything else could
+/// rewrite the request path or line
+fn validate_id(id: &str) -> Result<()> {
+    let mut chars = id.chars();
+    let valid = chars.next().is_some_and(|c| c.is_ascii_alphanumeric())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'));
+
+    if !valid {
+        return Err(PorterError::InvalidInput {
+            detail: format!("Invalid container ID: {:?}", id),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+/// The runtime and ID of the container a process runs in, read from its cgroup (Linux only)
+pub fn container_for_pid(pid: u32) -> Option<(&'static str, String)> {
+    #[cfg(target_os = "linux")]
+    {
+        crate::platform::cgroup_path(pid).and_then(|cgroup| container_from_cgroup(&cgroup))
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = pid;
+        None
+    }
+}
+
+/// Recognise `/system.slice/docker-<id>.scope`, `/docker/<id>`,
+/// `libpod-<id>.scope`, `cri-containerd-<id>.scope` and `crio-<id>.scope`
+pub fn container_from_cgroup(cgroup: &str) -> Option<(&'static str, String)> {
+    const PREFIXES: [(&str, &str); 5] = [
+        ("docker-", "docker"),
+        ("libpod-", "podman"),
+        ("cri-containerd-", "containerd"),
+        ("crio-", "cri-o"),
+        ("", "docker"),
+    ];
+
+    let components: Vec<&str> = cgroup.split('/').filter(|c| !c.is_empty()).collect();
+    for (index, component) in components.iter().enumerate().rev() {
+        let name = component.strip_suffix(".scope").unwrap_or(component);
+        for (prefix, runtime) in PREFIXES {
+            let Some(id) = name.strip_prefix(prefix) else {
+                continue;
+            };
+            // A bare ID only counts directly under a `docker` cgroup
+            if prefix.is_empty() && index.checked_sub(1).map(|parent| components[parent]) != Some("docker") {
+                continue;
+            }
+            if id.len() == 64 && id.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Some((runtime, id.to_string()));
+            }
+        }
+    }
+
+    None
+}
+
+fn parse_response(raw: &[u8]) -> Result<(u16, String)> {
+    let split = raw
+        .windows(4)
+        .position(|window| window == b"\r\n\r\n")
+        .ok_or_else(|| anyhow!("Malformed HTTP response from container runtime"))?;
+    let head = String::from_utf8_lossy(&raw[..split]);
+    let body = &raw[split + 4..];
+
+    let status = head
+        .lines()
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|code| code.parse().ok())
+        .ok_or_else(|| anyhow!("Malformed HTTP status line from container runtime"))?;
+
+    let chunked = head.lines().any(|line| {
+        let line = line.to_ascii_lowercase();
+        line.starts_with("transfer-encoding:") && line.contains("chunked")
+    });
+
+    let body = if chunked { decode_chunked(body)? } else { body.to_vec() };
+    Ok((status, String::from_utf8_lossy(&body).into_owned()))
+}
+
+fn decode_chunked(mut body: &[u8]) -> Result<Vec<u8>> {
+    let mut decoded = Vec::new();
+
+    loop {
+        let line_end = body
+            .windows(2)
+            .position(|window| window == b"\r\n")
+            .ok_or_else(|| anyhow!("Truncated chunked response from container runtime"))?;
+        let size_line = String::from_utf8_lossy(&body[..line_end]);
+        // Chunk extensions follow a ';'
+        let size = usize::from_str_radix(size_line.split(';').next().unwrap_or("").trim(), 16)?;
+        body = &body[line_end + 2..];
+
+        if size == 0 {
+            return Ok(decoded);
+        }
+        if body.len() < size {
+            return Err(anyhow!("Truncated chunked response from container runtime"));
+        }
+        decoded.extend_from_slice(&body[..size]);
+        body = body.get(size + 2..).unwrap_or_default();
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::net::UnixListener;
+
+    const CONTAINERS: &str = r#"[{"Id":"4f1c","Names":["/web"],"Image":"nginx","Ports":[
+        {"IP":"0.0.0.0","PrivatePort":80,"PublicPort":8080,"Type":"tcp"},
+        {"IP":"127.0.0.1","PrivatePort":53,"PublicPort":5353,"Type":"udp"},
+        {"PrivatePort":9000,"Type":"tcp"}],
+        "Labels":{"com.docker.compose.project":"shop"}}]"#;
+
+    /// Serve canned Engine API responses on a Unix socket in a temp dir
+    fn stub_runtime(dir: &tempfile::TempDir) -> ContainerRuntime {
+        let socket = dir.path().join("docker.sock");
+        let listener = UnixListener::bind(&socket).unwrap();
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut request_line = String::new();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                reader.read_line(&mut request_line).unwrap();
+                // Drain the headers
+                let mut line = String::new();
+                while reader.read_line(&mut line).unwrap() > 2 {
+                    line.clear();
+                }
+
+                let response = match request_line.split_whitespace().nth(1).unwrap_or_default() {
+                    "/containers/json" => {
+                        // Split the body over two chunks
+                        let (first, second) = CONTAINERS.split_at(CONTAINERS.len() / 2);
+                        format!(
+                            "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n{:x}\r\n{}\r\n{:x};ext=1\r\n{}\r\n0\r\n\r\n",
+                            first.len(),
+                            first,
+                            second.len(),
+                            second
+                        )
+                    }
+                    "/containers/running/stop?t=10" => "HTTP/1.1 204 No Content\r\n\r\n".to_string(),
+                    "/containers/stopped/stop?t=10" => "HTTP/1.1 304 Not Modified\r\n\r\n".to_string(),
+                    // Like a container that takes a while to handle SIGTERM
+                    "/containers/slow/stop?t=10" => {
+                        std::thread::sleep(Duration::from_secs(3));
+                        "HTTP/1.1 204 No Content\r\n\r\n".to_string()
+                    }
+                    "/containers/missing/stop?t=10" => {
+                        let body = r#"{"message":"No such container: missing"}"#;
+                        format!("HTTP/1.1 404 Not Found\r\nContent-Length: {}\r\n\r\n{}", body.len(), body)
+                    }
+                    _ => "HTTP/1.1 500 Internal Server Error\r\nContent-Length: 4\r\n\r\nboom".to_string(),
+                };
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+
+        ContainerRuntime::new("docker", socket)
+    }
+
+    #[test]
+    fn lists_published_ports_from_a_chunked_response() {
+        let dir = tempfile::tempdir().unwrap();
+        let published = stub_runtime(&dir).published_ports().unwrap();
+
+        assert_eq!(published.len(), 2);
+        assert_eq!(published[0].host_port, 8080);
+        assert_eq!(published[0].protocol, Protocol::TCP);
+        assert_eq!(published[0].container.name, "web");
+        assert_eq!(published[0].container.container_port, 80);
+        assert_eq!(published[0].container.compose_project.as_deref(), Some("shop"));
+        assert_eq!(published[1].host_ip, "127.0.0.1");
+        assert_eq!(published[1].protocol, Protocol::UDP);
+    }
+
+    #[test]
+    fn stop_reports_stopped_already_stopped_and_unknown_containers() {
+        let dir = tempfile::tempdir().unwrap();
+        let runtime = stub_runtime(&dir);
+
+        assert!(runtime.stop_container("running").unwrap());
+        assert!(runtime.stop_container("stopped").unwrap());
+        assert!(!runtime.stop_container("missing").unwrap());
+        assert!(runtime.stop_container("broken").is_err());
+    }
+
+    #[test]
+    fn stop_waits_past_the_api_timeout_for_the_container_to_exit() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(stub_runtime(&dir).stop_container("slow").unwrap());
+    }
+
+    #[test]
+    fn rejects_ids_that_could_rewrite_the_request() {
+        for id in ["", "../images", "web?force=1", "web\r\nHost: x", ".hidden", "a/b", "a b"] {
+            assert!(validate_id(id).is_err(), "{:?} was accepted", id);
+        }
+        for id in ["web", "4f1c9a", "shop_web.1", "shop-web-1"] {
+            assert!(validate_id(id).is_ok(), "{:?} was rejected", id);
+        }
+    }
+
+    #[test]
+    fn matches_publishes_on_protocol_and_address() {
+        let dir = tempfile::tempdir().unwrap();
+        let published = stub_runtime(&dir).published_ports().unwrap();
+
+        assert!(published_for(&published, Protocol::TCP, "0.0.0.0", 8080).is_some());
+        assert!(published_for(&published, Protocol::TCP, "127.0.0.1", 8080).is_none());
+        assert!(published_for(&published, Protocol::UDP, "0.0.0.0", 8080).is_none());
+        assert!(published_for(&published, Protocol::UDP, "::ffff:127.0.0.1", 5353).is_some());
+        assert!(published_for(&published, Protocol::TCP, "127.0.0.1", 5353).is_none());
+    }
+}
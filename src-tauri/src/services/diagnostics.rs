@@ -0,0 +1,70 @@
+use crate::models::{AttributionFailure, Capability, Diagnostics, UnattributedCount};
+use crate::platform;
+use super::admin;
+use anyhow::Result;
+use std::collections::HashMap;
+
+/// Summarize the scan backend, Porter's privileges and which sockets they hide
+pub fn collect() -> Result<Diagnostics> {
+    let (connections, details) = platform::get_network_connections_with_diagnostics()?;
+    let elevated = admin::is_elevated();
+
+    let mut counts: HashMap<AttributionFailure, usize> = HashMap::new();
+    for reason in connections.iter().filter_map(|conn| conn.unattributed) {
+        *counts.entry(reason).or_default() += 1;
+    }
+    let mut unattributed: Vec<UnattributedCount> = counts
+        .into_iter()
+        .map(|(reason, count)| UnattributedCount { reason, count })
+        .collect();
+    unattributed.sort_by_key(|entry| std::cmp::Reverse(entry.count));
+
+    let mut notes = Vec::new();
+    if let Some(mode) = &details.hidepid {
+        notes.push(format!(
+            "/proc is mounted with hidepid={}, so other users' processes are invisible to Porter.",
+            mode
+        ));
+    }
+    if details.unreadable_processes > 0 && !elevated {
+        notes.push(format!(
+            "{} processes belong to other users and their sockets cannot be attributed. \
+             Run Porter with sudo or grant it CAP_SYS_PTRACE and CAP_DAC_READ_SEARCH to see them.",
+            details.unreadable_processes
+        ));
+    }
+    if details.isolated_pid_namespace {
+        notes.push(
+            "Porter runs in a nested PID namespace (e.g. a container); owners outside it cannot be attributed."
+                .to_string(),
+        );
+    }
+    if details
+        .capabilities
+        .iter()
+        .any(|(name, effective)| name == "CAP_KILL" && !effective)
+        && !elevated
+    {
+        notes.push("Without CAP_KILL only your own processes can be killed.".to_string());
+    }
+    if cfg!(target_os = "macos") && !elevated {
+        notes.push("lsof only lists other users' sockets when Porter runs as root.".to_string());
+    }
+
+    Ok(Diagnostics {
+        os: std::env::consts::OS.to_string(),
+        backend: details.backend.to_string(),
+        elevated,
+        capabilities: details
+            .capabilities
+            .into_iter()
+            .map(|(name, effective)| Capability { name, effective })
+            .collect(),
+        hidepid: details.hidepid,
+        isolated_pid_namespace: details.isolated_pid_namespace,
+        unreadable_processes: details.unreadable_processes,
+        total_sockets: connections.len(),
+        unattributed,
+        notes,
+    })
+}
@@ -0,0 +1,61 @@
+use crate::models::PortRange;
+use super::ProcFs;
+use std::collections::HashSet;
+
+/// IANA dynamic range, the default on macOS and Windows
+const IANA_DYNAMIC: PortRange = PortRange {
+    start: 49152,
+    end: 65535,
+};
+
+/// The ports the kernel may pick for outgoing connections
+pub struct EphemeralRange {
+    range: Option<PortRange>,
+    /// `ip_local_reserved_ports` entries the allocator skips
+    reserved: Vec<PortRange>,
+}
+
+impl EphemeralRange {
+    pub fn read(proc: &ProcFs) -> Self {
+        let range = proc.ephemeral_port_range();
+        let range = if cfg!(target_os = "linux") {
+            range
+        } else {
+            range.or(Some(IANA_DYNAMIC))
+        };
+
+        Self {
+            range,
+            reserved: proc.reserved_ports(),
+        }
+    }
+
+    pub fn range(&self) -> Option<PortRange> {
+        self.range
+    }
+
+    /// Whether an outgoing connection could be handed this port
+    pub fn contains(&self, port: u16) -> bool {
+        self.range.is_some_and(|range| range.contains(port))
+            && !self.reserved.iter().any(|reserved| reserved.contains(port))
+    }
+
+    /// The nearest ports outside the ephemeral range that nothing is using
+    pub fn alternatives(&self, port: u16, in_use: &HashSet<u16>, count: usize) -> Vec<u16> {
+        let usable = |candidate: u16| candidate >= 1024 && !self.contains(candidate) && !in_use.contains(&candidate);
+        let mut found = Vec::new();
+
+        for distance in 1..=u16::MAX {
+            let below = port.checked_sub(distance).filter(|&p| usable(p));
+            let above = port.checked_add(distance).filter(|&p| usable(p));
+            found.extend(below.into_iter().chain(above));
+
+            if found.len() >= count || (port < distance && port.checked_add(distance).is_none()) {
+                break;
+            }
+        }
+
+        found.truncate(count);
+        found
+    }
+}
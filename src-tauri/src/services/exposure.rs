@@ -0,0 +1,225 @@
+use crate::models::{Exposure, ExposureEntry, ExposureReport, InterfaceKind, NetworkInterface};
+use std::net::{IpAddr, Ipv6Addr};
+
+/// Ports of services that should never be reachable from the network on a dev machine
+const SENSITIVE_PORTS: &[(u16, &str)] = &[
+    (1433, "SQL Server"),
+    (1521, "Oracle"),
+    (2375, "Docker API (unencrypted)"),
+    (2379, "etcd"),
+    (3306, "MySQL"),
+    (3389, "RDP"),
+    (5005, "JVM debugger"),
+    (5432, "PostgreSQL"),
+    (5672, "RabbitMQ"),
+    (5900, "VNC"),
+    (5984, "CouchDB"),
+    (6379, "Redis"),
+    (8086, "InfluxDB"),
+    (9042, "Cassandra"),
+    (9092, "Kafka"),
+    (9200, "Elasticsearch"),
+    (9229, "Node.js inspector"),
+    (11211, "Memcached"),
+    (27017, "MongoDB"),
+];
+
+/// Process names of the same services, for when they run on a non-default port
+const SENSITIVE_PROCESSES: &[(&str, &str)] = &[
+    ("postgres", "PostgreSQL"),
+    ("mysqld", "MySQL"),
+    ("mariadbd", "MariaDB"),
+    ("redis-server", "Redis"),
+    ("mongod", "MongoDB"),
+    ("memcached", "Memcached"),
+];
+
+/// Classify a bound address by the interface that holds it. A private address
+/// only counts as LAN on a physical, wireless or bridge interface; on a VPN
+/// tunnel or container bridge it reaches just that network.
+pub fn classify(address: &str, interfaces: &[NetworkInterface]) -> (Exposure, Option<String>) {
+    let Ok(ip) = address.parse::<IpAddr>() else {
+        return (Exposure::AllInterfaces, None);
+    };
+    // A v4-mapped bind like ::ffff:127.0.0.1 behaves like its IPv4 address
+    let ip = match ip {
+        IpAddr::V6(v6) => v6.to_ipv4_mapped().map(IpAddr::V4).unwrap_or(ip),
+        v4 => v4,
+    };
+
+    if ip.is_unspecified() {
+        return (Exposure::AllInterfaces, None);
+    }
+
+    let owner = interfaces.iter().find(|iface| {
+        iface
+            .addresses
+            .iter()
+            .any(|addr| addr.address.parse::<IpAddr>().ok() == Some(ip))
+    });
+    let interface = owner.map(|iface| iface.name.clone());
+
+    let exposure = match owner.map(|iface| iface.kind) {
+        _ if ip.is_loopback() => Exposure::LoopbackOnly,
+        Some(InterfaceKind::Loopback) => Exposure::LoopbackOnly,
+        Some(InterfaceKind::Physical | InterfaceKind::Wireless | InterfaceKind::Bridge) if is_private(&ip) => {
+            Exposure::Lan
+        }
+        // Also an address no interface holds yet (IP_FREEBIND), reachable once one does
+        _ => Exposure::SpecificInterface,
+    };
+
+    (exposure, interface)
+}
+
+fn is_private(ip: &IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => v4.is_private() || v4.is_link_local(),
+        IpAddr::V6(v6) => is_unique_local(v6) || is_unicast_link_local(v6),
+    }
+}
+
+/// fc00::/7
+fn is_unique_local(ip: &Ipv6Addr) -> bool {
+    ip.segments()[0] & 0xfe00 == 0xfc00
+}
+
+/// fe80::/10
+fn is_unicast_link_local(ip: &Ipv6Addr) -> bool {
+    ip.segments()[0] & 0xffc0 == 0xfe80
+}
+
+/// Name of the sensitive service behind a listener, if it is one
+pub fn sensitive_service(port: u16, process_name: Option<&str>) -> Option<String> {
+    let by_process = process_name.and_then(|name| {
+        SENSITIVE_PROCESSES
+            .iter()
+            .find(|(prefix, _)| name.starts_with(prefix))
+            .map(|(_, service)| service.to_string())
+    });
+
+    by_process.or_else(|| {
+        SENSITIVE_PORTS
+            .iter()
+            .find(|(known, _)| *known == port)
+            .map(|(_, service)| service.to_string())
+    })
+}
+
+/// Flagged entries first, then by port
+pub fn sort_entries(entries: &mut [ExposureEntry]) {
+    entries.sort_by_key(|entry| (!entry.flagged, entry.port));
+}
+
+pub fn render_markdown(report: &ExposureReport) -> String {
+    let mut out = format!(
+        "# Network exposure report\n\nHost: {}  \nGenerated: {}\n\n",
+        report.hostname,
+        report.generated_at.format("%Y-%m-%d %H:%M:%S UTC")
+    );
+
+    let flagged = report.listeners.iter().filter(|entry| entry.flagged).count();
+    if flagged > 0 {
+        out.push_str(&format!(
+            "**{} sensitive service(s) reachable beyond loopback.**\n\n",
+            flagged
+        ));
+    }
+
+    out.push_str("| Port | Address | Exposure | Service | Process | |\n");
+    out.push_str("|---|---|---|---|---|---|\n");
+    for entry in &report.listeners {
+        let process = match (&entry.process_name, entry.pid) {
+            (Some(name), Some(pid)) => format!("{} ({})", name, pid),
+            _ => "-".to_string(),
+        };
+        out.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} |\n",
+            entry.port,
+            entry.local_address,
+            exposure_label(entry),
+            entry.service.as_deref().unwrap_or("-"),
+            process,
+            if entry.flagged { "⚠" } else { "" }
+        ));
+    }
+
+    out.push_str("\n## Interfaces\n\n");
+    for iface in &report.interfaces {
+        out.push_str(&format!(
+            "- {}: {}/{}{}\n",
+            iface.interface,
+            iface.address,
+            iface.prefix_len,
+            if iface.up { "" } else { " (down)" }
+        ));
+    }
+
+    out
+}
+
+pub fn exposure_label(entry: &ExposureEntry) -> String {
+    let label = match entry.exposure {
+        Exposure::LoopbackOnly => "loopback only",
+        Exposure::Lan => "LAN",
+        Exposure::SpecificInterface => "specific interface",
+        Exposure::AllInterfaces => "all interfaces",
+    };
+
+    match &entry.interface {
+        Some(iface) if entry.exposure != Exposure::LoopbackOnly => format!("{} ({})", label, iface),
+        _ => label.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::InterfaceAddress;
+
+    fn interface(name: &str, kind: InterfaceKind, address: &str) -> NetworkInterface {
+        NetworkInterface {
+            name: name.to_string(),
+            kind,
+            up: true,
+            addresses: vec![InterfaceAddress {
+                interface: name.to_string(),
+                address: address.to_string(),
+                prefix_len: 24,
+                loopback: kind == InterfaceKind::Loopback,
+                up: true,
+            }],
+        }
+    }
+
+    #[test]
+    fn classifies_private_addresses_by_their_interface() {
+        let interfaces = [
+            interface("lo", InterfaceKind::Loopback, "127.0.0.1"),
+            interface("eth0", InterfaceKind::Physical, "192.168.1.20"),
+            interface("wg0", InterfaceKind::Virtual, "10.8.0.2"),
+            interface("docker0", InterfaceKind::ContainerBridge, "172.17.0.1"),
+            interface("wlan0", InterfaceKind::Wireless, "203.0.113.7"),
+        ];
+
+        let cases = [
+            ("0.0.0.0", Exposure::AllInterfaces, None),
+            ("::", Exposure::AllInterfaces, None),
+            ("127.0.0.2", Exposure::LoopbackOnly, None),
+            ("::ffff:127.0.0.1", Exposure::LoopbackOnly, Some("lo")),
+            ("192.168.1.20", Exposure::Lan, Some("eth0")),
+            ("10.8.0.2", Exposure::SpecificInterface, Some("wg0")),
+            ("172.17.0.1", Exposure::SpecificInterface, Some("docker0")),
+            ("203.0.113.7", Exposure::SpecificInterface, Some("wlan0")),
+            ("192.168.1.99", Exposure::SpecificInterface, None),
+        ];
+        for (address, exposure, owner) in cases {
+            assert_eq!(
+                classify(address, &interfaces),
+                (exposure, owner.map(str::to_string)),
+                "{}",
+                address
+            );
+        }
+    }
+}
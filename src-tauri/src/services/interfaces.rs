@@ -0,0 +1,260 @@
+use super::ProcFs;
+use crate::models::{InterfaceAddress, InterfaceKind, NetworkInterface};
+use std::net::IpAddr;
+
+/// Every local interface with its addresses, in the order the OS lists them
+pub fn network_interfaces() -> Vec<NetworkInterface> {
+    let mut interfaces: Vec<NetworkInterface> = Vec::new();
+
+    for raw in raw_interfaces() {
+        let index = match interfaces.iter().position(|iface| iface.name == raw.name) {
+            Some(index) => index,
+            None => {
+                interfaces.push(NetworkInterface {
+                    kind: interface_kind(&raw.name, raw.loopback),
+                    name: raw.name.clone(),
+                    up: raw.up,
+                    addresses: Vec::new(),
+                });
+                interfaces.len() - 1
+            }
+        };
+
+        if let Some((address, prefix_len)) = raw.address {
+            interfaces[index].addresses.push(InterfaceAddress {
+                interface: raw.name,
+                address: address.to_string(),
+                prefix_len,
+                loopback: raw.loopback,
+                up: raw.up,
+            });
+        }
+    }
+
+    interfaces
+}
+
+/// Every address assigned to a local interface
+pub fn interface_addresses() -> Vec<InterfaceAddress> {
+    network_interfaces()
+        .into_iter()
+        .flat_map(|iface| iface.addresses)
+        .collect()
+}
+
+/// Whether IPv6 sockets are v6-only unless they say otherwise: `net.ipv6.bindv6only`
+/// on Linux, always on Windows, never on macOS
+pub fn v6_only_default() -> bool {
+    if cfg!(target_os = "windows") {
+        return true;
+    }
+    ProcFs::new().bindv6only().unwrap_or(false)
+}
+
+/// Interfaces a listener bound to `address` can be reached on.
+/// Wildcard binds cover every interface of their family; `::` also accepts
+/// IPv4 unless the socket is `v6_only`.
+pub fn reachable_on<'a>(address: &str, v6_only: bool, interfaces: &'a [NetworkInterface]) -> Vec<&'a NetworkInterface> {
+    let Ok(ip) = address.parse::<IpAddr>() else {
+        return Vec::new();
+    };
+    let ip = match ip {
+        IpAddr::V6(v6) => v6.to_ipv4_mapped().map(IpAddr::V4).unwrap_or(ip),
+        v4 => v4,
+    };
+
+    let holds = |iface: &NetworkInterface, pred: &dyn Fn(IpAddr) -> bool| {
+        iface
+            .addresses
+            .iter()
+            .filter_map(|addr| addr.address.parse::<IpAddr>().ok())
+            .any(pred)
+    };
+
+    let matching: Vec<&NetworkInterface> = match ip {
+        IpAddr::V4(v4) if v4.is_unspecified() => interfaces
+            .iter()
+            .filter(|iface| holds(iface, &|addr| addr.is_ipv4()))
+            .collect(),
+        IpAddr::V6(v6) if v6.is_unspecified() && v6_only => interfaces
+            .iter()
+            .filter(|iface| holds(iface, &|addr| addr.is_ipv6()))
+            .collect(),
+        IpAddr::V6(v6) if v6.is_unspecified() => interfaces
+            .iter()
+            .filter(|iface| !iface.addresses.is_empty())
+            .collect(),
+        _ => interfaces
+            .iter()
+            .filter(|iface| holds(iface, &|addr| addr == ip))
+            .collect(),
+    };
+
+    // 127.0.0.2 and friends are served by lo without being listed on it
+    if matching.is_empty() && ip.is_loopback() {
+        return interfaces
+            .iter()
+            .filter(|iface| iface.kind == InterfaceKind::Loopback)
+            .collect();
+    }
+
+    matching
+}
+
+fn interface_kind(name: &str, loopback: bool) -> InterfaceKind {
+    const CONTAINER_BRIDGES: [&str; 4] = ["docker", "br-", "podman", "cni"];
+
+    if loopback {
+        return InterfaceKind::Loopback;
+    }
+    if CONTAINER_BRIDGES.iter().any(|prefix| name.starts_with(prefix)) {
+        return InterfaceKind::ContainerBridge;
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let sys = std::path::Path::new("/sys/class/net").join(name);
+        if sys.join("wireless").exists() || sys.join("phy80211").exists() {
+            return InterfaceKind::Wireless;
+        }
+        if sys.join("bridge").exists() {
+            return InterfaceKind::Bridge;
+        }
+        // Only hardware-backed interfaces have a device link
+        if sys.join("device").exists() {
+            return InterfaceKind::Physical;
+        }
+        InterfaceKind::Virtual
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        if name.starts_with("bridge") {
+            InterfaceKind::Bridge
+        } else if name.starts_with("en") || name.starts_with("eth") || name.starts_with("Ethernet") {
+            InterfaceKind::Physical
+        } else if name.starts_with("Wi-Fi") || name.starts_with("wl") {
+            InterfaceKind::Wireless
+        } else {
+            InterfaceKind::Virtual
+        }
+    }
+}
+
+/// One `getifaddrs` entry; interfaces without an IP address still appear
+/// once through their link-layer entry
+struct RawInterface {
+    name: String,
+    up: bool,
+    loopback: bool,
+    /// Address and prefix length, for IPv4 and IPv6 entries
+    address: Option<(IpAddr, u8)>,
+}
+
+#[cfg(unix)]
+fn raw_interfaces() -> Vec<RawInterface> {
+    use std::ffi::CStr;
+
+    let mut head: *mut libc::ifaddrs = std::ptr::null_mut();
+    if unsafe { libc::getifaddrs(&mut head) } != 0 {
+        return Vec::new();
+    }
+
+    let mut entries = Vec::new();
+    let mut cursor = head;
+
+    while !cursor.is_null() {
+        let entry = unsafe { &*cursor };
+        cursor = entry.ifa_next;
+
+        let address = unsafe { sockaddr_ip(entry.ifa_addr) }.map(|address| {
+            let prefix_len = match unsafe { sockaddr_ip(entry.ifa_netmask) } {
+                Some(IpAddr::V4(mask)) => u32::from(mask).count_ones() as u8,
+                Some(IpAddr::V6(mask)) => u128::from(mask).count_ones() as u8,
+                None => 0,
+            };
+            (address, prefix_len)
+        });
+
+        entries.push(RawInterface {
+            name: unsafe { CStr::from_ptr(entry.ifa_name) }.to_string_lossy().into_owned(),
+            up: entry.ifa_flags & libc::IFF_UP as libc::c_uint != 0,
+            loopback: entry.ifa_flags & libc::IFF_LOOPBACK as libc::c_uint != 0,
+            address,
+        });
+    }
+
+    unsafe { libc::freeifaddrs(head) };
+    entries
+}
+
+#[cfg(not(unix))]
+fn raw_interfaces() -> Vec<RawInterface> {
+    Vec::new()
+}
+
+/// IP address held in a `sockaddr`, for the IPv4 and IPv6 families only
+#[cfg(unix)]
+unsafe fn sockaddr_ip(addr: *const libc::sockaddr) -> Option<IpAddr> {
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    if addr.is_null() {
+        return None;
+    }
+
+    match (*addr).sa_family as libc::c_int {
+        libc::AF_INET => {
+            let sin = &*(addr as *const libc::sockaddr_in);
+            Some(IpAddr::V4(Ipv4Addr::from(u32::from_be(sin.sin_addr.s_addr))))
+        }
+        libc::AF_INET6 => {
+            let sin6 = &*(addr as *const libc::sockaddr_in6);
+            Some(IpAddr::V6(Ipv6Addr::from(sin6.sin6_addr.s6_addr)))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interface(name: &str, kind: InterfaceKind, addresses: &[&str]) -> NetworkInterface {
+        NetworkInterface {
+            name: name.to_string(),
+            kind,
+            up: true,
+            addresses: addresses
+                .iter()
+                .map(|address| InterfaceAddress {
+                    interface: name.to_string(),
+                    address: address.to_string(),
+                    prefix_len: 64,
+                    loopback: kind == InterfaceKind::Loopback,
+                    up: true,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn v6_only_wildcards_skip_ipv4_only_interfaces() {
+        let interfaces = [
+            interface("lo", InterfaceKind::Loopback, &["127.0.0.1", "::1"]),
+            interface("eth0", InterfaceKind::Physical, &["192.168.1.20", "fe80::1"]),
+            interface("wg0", InterfaceKind::Virtual, &["10.8.0.2"]),
+        ];
+        let names = |address: &str, v6_only: bool| -> Vec<String> {
+            reachable_on(address, v6_only, &interfaces)
+                .into_iter()
+                .map(|iface| iface.name.clone())
+                .collect()
+        };
+
+        assert_eq!(names("::", false), ["lo", "eth0", "wg0"]);
+        assert_eq!(names("::", true), ["lo", "eth0"]);
+        assert_eq!(names("0.0.0.0", false), ["lo", "eth0", "wg0"]);
+        assert_eq!(names("::ffff:10.8.0.2", true), ["wg0"]);
+        assert_eq!(names("127.0.0.53", false), ["lo"]);
+    }
+}
@@ -0,0 +1,184 @@
+use crate::models::{ImpactedClient, RestartManager};
+use crate::platform::NetworkConnection;
+use std::collections::BTreeSet;
+
+/// Whether `pid` holds a socket, including one it shares with a pre-fork master or sibling workers
+pub fn held_by(conn: &NetworkConnection, pid: u32) -> bool {
+    conn.pid == pid || conn.shared_with.contains(&pid)
+}
+
+/// Ports `pid` listens on, sorted
+pub fn listening_ports(connections: &[NetworkConnection], pid: u32) -> Vec<u16> {
+    connections
+        .iter()
+        .filter(|conn| held_by(conn, pid) && conn.is_listening())
+        .map(|conn| conn.local_port)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// Connections accepted on `pid`'s listeners, with the local client process when
+/// the other end lives on this machine
+pub fn clients(
+    connections: &[NetworkConnection],
+    pid: u32,
+    listening_ports: &[u16],
+    process_name: impl Fn(u32) -> Option<String>,
+) -> Vec<ImpactedClient> {
+    let mut clients = Vec::new();
+    for conn in connections.iter().filter(|conn| {
+        held_by(conn, pid) && conn.state == "ESTABLISHED" && listening_ports.contains(&conn.local_port)
+    }) {
+        // The client's end of the same connection, if it lives on this machine
+        let peer = connections.iter().find(|other| {
+            other.local_port == conn.remote_port
+                && other.remote_port == conn.local_port
+                && other.local_address == conn.remote_address
+        });
+        let peer_pid = peer.map(|peer| peer.pid).filter(|&pid| pid > 0);
+
+        clients.push(ImpactedClient {
+            port: conn.local_port,
+            remote_address: conn.remote_address.clone(),
+            remote_port: conn.remote_port,
+            local: peer.is_some(),
+            pid: peer_pid,
+            process_name: peer_pid.and_then(&process_name),
+        });
+    }
+
+    clients
+}
+
+/// e.g. "Killing api (PID 12) drops 3 connections (2 local: psql, web; 1 remote),
+/// leaves 1 child process running, and systemd may restart it (api.service)."
+pub fn summarize(
+    name: &str,
+    pid: u32,
+    clients: &[ImpactedClient],
+    children: usize,
+    restarted_by: &[RestartManager],
+) -> String {
+    let mut effects = Vec::new();
+
+    if !clients.is_empty() {
+        let local: Vec<&ImpactedClient> = clients.iter().filter(|client| client.local).collect();
+        let remote = clients.len() - local.len();
+
+        let mut names: Vec<&str> = local
+            .iter()
+            .filter_map(|client| client.process_name.as_deref())
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+
+        let mut detail = Vec::new();
+        if !local.is_empty() {
+            if names.is_empty() {
+                detail.push(format!("{} local", local.len()));
+            } else {
+                detail.push(format!("{} local: {}", local.len(), names.join(", ")));
+            }
+        }
+        if remote > 0 {
+            detail.push(format!("{} remote", remote));
+        }
+        effects.push(format!("drops {} ({})", plural(clients.len(), "connection"), detail.join("; ")));
+    }
+
+    if children > 0 {
+        // SIGTERM only reaches the target; children are re-parented unless they exit on their own
+        effects.push(format!("may leave {} running", plural(children, "child process")));
+    }
+
+    for manager in restarted_by {
+        effects.push(match manager {
+            RestartManager::Systemd { unit } => format!("systemd may restart it ({})", unit.name),
+            RestartManager::Container { runtime, id, name } => format!(
+                "{} may restart it (container {})",
+                runtime,
+                name.clone().unwrap_or_else(|| id.chars().take(12).collect())
+            ),
+        });
+    }
+
+    let target = format!("{} (PID {})", name, pid);
+    match effects.len() {
+        0 => format!("Killing {} affects no connections or other processes.", target),
+        1 => format!("Killing {} {}.", target, effects[0]),
+        _ => {
+            let last = effects.pop().unwrap_or_default();
+            format!("Killing {} {}, and {}.", target, effects.join(", "), last)
+        }
+    }
+}
+
+fn plural(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("1 {}", noun)
+    } else if let Some(stem) = noun.strip_suffix("ss") {
+        format!("{} {}sses", count, stem)
+    } else {
+        format!("{} {}s", count, noun)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Protocol;
+
+    fn tcp(local: (&str, u16), remote: (&str, u16), state: &str, pid: u32, shared_with: &[u32]) -> NetworkConnection {
+        NetworkConnection {
+            local_address: local.0.to_string(),
+            local_port: local.1,
+            remote_address: remote.0.to_string(),
+            remote_port: remote.1,
+            protocol: Protocol::TCP,
+            pid,
+            shared_with: shared_with.to_vec(),
+            state: state.to_string(),
+            unattributed: None,
+            namespace: None,
+            tcp: None,
+        }
+    }
+
+    /// A gunicorn-style master (100) with two workers (101, 102) sharing its
+    /// listener; worker 102 accepted a connection from a local psql (200)
+    fn prefork_server() -> Vec<NetworkConnection> {
+        vec![
+            tcp(("0.0.0.0", 8000), ("0.0.0.0", 0), "LISTEN", 100, &[101, 102]),
+            tcp(("127.0.0.1", 8000), ("127.0.0.1", 51000), "ESTABLISHED", 100, &[101, 102]),
+            tcp(("127.0.0.1", 51000), ("127.0.0.1", 8000), "ESTABLISHED", 200, &[]),
+            tcp(("0.0.0.0", 5432), ("0.0.0.0", 0), "LISTEN", 300, &[]),
+        ]
+    }
+
+    #[test]
+    fn workers_sharing_a_listener_see_its_ports_and_clients() {
+        let connections = prefork_server();
+        let name = |pid: u32| (pid == 200).then(|| "psql".to_string());
+
+        for pid in [100, 101, 102] {
+            let ports = listening_ports(&connections, pid);
+            assert_eq!(ports, [8000], "PID {}", pid);
+
+            let clients = clients(&connections, pid, &ports, name);
+            assert_eq!(clients.len(), 1, "PID {}", pid);
+            assert!(clients[0].local);
+            assert_eq!(clients[0].pid, Some(200));
+            assert_eq!(clients[0].process_name.as_deref(), Some("psql"));
+        }
+    }
+
+    #[test]
+    fn unrelated_processes_see_nothing() {
+        let connections = prefork_server();
+
+        assert_eq!(listening_ports(&connections, 300), [5432]);
+        assert!(listening_ports(&connections, 200).is_empty());
+        assert!(clients(&connections, 300, &[5432], |_| None).is_empty());
+    }
+}
@@ -0,0 +1,218 @@
+use crate::models::{LeakKind, LeakWarning, ProcessSocketUsage, SocketUsageSample};
+
+/// Descriptor usage at which a process is flagged regardless of trend
+const FD_WARNING_RATIO: f32 = 0.8;
+/// Flag steady descriptor growth that would reach the limit within this many seconds
+const FD_EXHAUSTION_HORIZON_SECS: f64 = 600.0;
+/// CLOSE_WAIT sockets flagged even without history
+const CLOSE_WAIT_PILE: usize = 50;
+/// Growth only counts once at least this many CLOSE_WAIT sockets are held
+const CLOSE_WAIT_GROWTH_FLOOR: usize = 5;
+/// Samples needed before a trend is trusted
+const MIN_TREND_SAMPLES: usize = 3;
+
+/// Warnings for one process, judged from its sample history (oldest first)
+pub fn warnings(history: &[SocketUsageSample], fd_limit: Option<u64>) -> Vec<LeakWarning> {
+    fd_warning(history, fd_limit)
+        .into_iter()
+        .chain(close_wait_warning(history))
+        .collect()
+}
+
+fn fd_warning(history: &[SocketUsageSample], fd_limit: Option<u64>) -> Option<LeakWarning> {
+    let limit = fd_limit.filter(|&limit| limit > 0)?;
+    let current = history.last()?;
+    let open = current.open_fds?;
+
+    let usage = open as f32 / limit as f32;
+    if usage >= FD_WARNING_RATIO {
+        return Some(LeakWarning {
+            kind: LeakKind::FdExhaustion,
+            message: format!(
+                "Using {} of {} file descriptors ({:.0}%); new sockets will fail with EMFILE at the limit",
+                open,
+                limit,
+                usage * 100.0
+            ),
+        });
+    }
+
+    let counts: Vec<(&SocketUsageSample, u64)> = history
+        .iter()
+        .filter_map(|sample| Some((sample, sample.open_fds?)))
+        .collect();
+    if counts.len() < MIN_TREND_SAMPLES || !counts.windows(2).all(|pair| pair[1].1 >= pair[0].1) {
+        return None;
+    }
+
+    let (first, first_open) = counts[0];
+    let elapsed = (current.timestamp - first.timestamp).num_milliseconds() as f64 / 1000.0;
+    if open <= first_open || elapsed <= 0.0 {
+        return None;
+    }
+
+    let per_second = (open - first_open) as f64 / elapsed;
+    let remaining = limit.saturating_sub(open) as f64 / per_second;
+    (remaining <= FD_EXHAUSTION_HORIZON_SECS).then(|| LeakWarning {
+        kind: LeakKind::FdExhaustion,
+        message: format!(
+            "File descriptors grew from {} to {} in {:.0}s; at this rate the limit of {} is reached in about {}",
+            first_open,
+            open,
+            elapsed,
+            limit,
+            format_duration(remaining)
+        ),
+    })
+}
+
+fn close_wait_warning(history: &[SocketUsageSample]) -> Option<LeakWarning> {
+    let current = history.last()?;
+
+    if current.close_wait >= CLOSE_WAIT_PILE {
+        return Some(LeakWarning {
+            kind: LeakKind::CloseWait,
+            message: format!(
+                "Holding {} CLOSE_WAIT sockets: peers closed these connections but the process never did",
+                current.close_wait
+            ),
+        });
+    }
+
+    let first = history.first()?;
+    let growing = history.len() >= MIN_TREND_SAMPLES
+        && current.close_wait >= CLOSE_WAIT_GROWTH_FLOOR
+        && current.close_wait > first.close_wait
+        && history.windows(2).all(|pair| pair[1].close_wait >= pair[0].close_wait);
+
+    growing.then(|| LeakWarning {
+        kind: LeakKind::CloseWait,
+        message: format!(
+            "CLOSE_WAIT sockets grew from {} to {} over {}s without any being closed",
+            first.close_wait,
+            current.close_wait,
+            (current.timestamp - first.timestamp).num_seconds()
+        ),
+    })
+}
+
+/// Processes with warnings first, then the closest to their descriptor limit
+pub fn sort_usage(processes: &mut [ProcessSocketUsage]) {
+    processes.sort_by(|a, b| {
+        b.warnings
+            .len()
+            .cmp(&a.warnings.len())
+            .then(b.fd_usage.unwrap_or(0.0).total_cmp(&a.fd_usage.unwrap_or(0.0)))
+            .then(b.close_wait.cmp(&a.close_wait))
+    });
+}
+
+fn format_duration(seconds: f64) -> String {
+    if seconds < 90.0 {
+        format!("{:.0}s", seconds.max(1.0))
+    } else {
+        format!("{:.0} min", seconds / 60.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone, Utc};
+
+    /// Samples as `(seconds since the first, CLOSE_WAIT count, open fds)`
+    type Samples<'a> = &'a [(i64, usize, Option<u64>)];
+
+    fn history(samples: Samples) -> Vec<SocketUsageSample> {
+        let start = Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap();
+        samples
+            .iter()
+            .map(|&(secs, close_wait, open_fds)| SocketUsageSample {
+                timestamp: start + Duration::seconds(secs),
+                close_wait,
+                open_fds,
+            })
+            .collect()
+    }
+
+    fn kinds(samples: Samples, fd_limit: Option<u64>) -> Vec<LeakKind> {
+        warnings(&history(samples), fd_limit).into_iter().map(|w| w.kind).collect()
+    }
+
+    #[test]
+    fn descriptor_thresholds() {
+        use LeakKind::FdExhaustion;
+
+        let cases: [(&str, Samples, Option<u64>, &[LeakKind]); 9] = [
+            ("at 80% of the limit", &[(0, 0, Some(800))], Some(1000), &[FdExhaustion]),
+            ("just under 80%", &[(0, 0, Some(799))], Some(1000), &[]),
+            ("no limit known", &[(0, 0, Some(5000))], None, &[]),
+            ("unlimited", &[(0, 0, Some(5000))], Some(0), &[]),
+            ("fd count unreadable", &[(0, 0, None)], Some(1000), &[]),
+            (
+                "limit reached in ~7 minutes",
+                &[(0, 0, Some(100)), (60, 0, Some(200)), (120, 0, Some(300))],
+                Some(1024),
+                &[FdExhaustion],
+            ),
+            (
+                "limit reached in ~90 minutes",
+                &[(0, 0, Some(100)), (60, 0, Some(110)), (120, 0, Some(120))],
+                Some(1024),
+                &[],
+            ),
+            (
+                "growth that dipped",
+                &[(0, 0, Some(100)), (60, 0, Some(300)), (120, 0, Some(290))],
+                Some(1024),
+                &[],
+            ),
+            ("too few samples for a trend", &[(0, 0, Some(100)), (60, 0, Some(500))], Some(1024), &[]),
+        ];
+
+        for (name, samples, limit, expected) in cases {
+            assert_eq!(kinds(samples, limit), expected, "{}", name);
+        }
+    }
+
+    #[test]
+    fn close_wait_thresholds() {
+        use LeakKind::CloseWait;
+
+        let cases: [(&str, Samples, &[LeakKind]); 6] = [
+            ("a pile without history", &[(0, 50, None)], &[CloseWait]),
+            ("just under the pile", &[(0, 49, None)], &[]),
+            ("steady growth", &[(0, 1, None), (5, 3, None), (10, 5, None)], &[CloseWait]),
+            ("growth below the floor", &[(0, 1, None), (5, 2, None), (10, 4, None)], &[]),
+            ("flat", &[(0, 5, None), (5, 5, None), (10, 5, None)], &[]),
+            ("some were closed", &[(0, 2, None), (5, 6, None), (10, 5, None)], &[]),
+        ];
+
+        for (name, samples, expected) in cases {
+            assert_eq!(kinds(samples, None), expected, "{}", name);
+        }
+    }
+
+    #[test]
+    fn both_warnings_are_reported_with_their_numbers() {
+        let warnings = warnings(
+            &history(&[(0, 10, Some(100)), (60, 20, Some(200)), (120, 30, Some(300))]),
+            Some(1024),
+        );
+
+        let messages: Vec<_> = warnings.iter().map(|w| (w.kind, w.message.as_str())).collect();
+        assert_eq!(
+            messages,
+            [
+                (
+                    LeakKind::FdExhaustion,
+                    "File descriptors grew from 100 to 300 in 120s; at this rate the limit of 1024 is reached in about 7 min"
+                ),
+                (
+                    LeakKind::CloseWait,
+                    "CLOSE_WAIT sockets grew from 10 to 30 over 120s without any being closed"
+                ),
+            ]
+        );
+    }
+}
@@ -1,6 +1,28 @@
 pub mod port_monitor;
 pub mod process_manager;
 pub mod admin;
+pub mod audit_log;
+pub mod containers;
+pub mod diagnostics;
+pub mod ephemeral;
+pub mod exposure;
+pub mod interfaces;
+pub mod kill_impact;
+pub mod leaks;
+pub mod pause_store;
+pub mod port_diagnosis;
+pub mod procfs;
+pub mod process_tree;
+pub mod reservations;
+pub mod service_graph;
+pub mod storage;
+pub mod system_info;
+pub mod systemd;
+pub mod users;
 
+pub use audit_log::*;
 pub use port_monitor::*;
 pub use process_manager::*;
+pub use pause_store::*;
+pub use procfs::ProcFs;
+pub use users::UserResolver;
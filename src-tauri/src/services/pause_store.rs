@@ -0,0 +1,35 @@
+use crate::models::PausedProcess;
+use super::storage;
+use anyhow::Result;
+use std::fs;
+use std::path::PathBuf;
+
+/// Persists the processes Porter has paused so the GUI and CLI see the same list
+pub struct PauseStore {
+    path: PathBuf,
+}
+
+impl PauseStore {
+    pub fn new() -> Self {
+        Self::with_path(storage::data_dir().join("paused.json"))
+    }
+
+    pub fn with_path(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn load(&self) -> Vec<PausedProcess> {
+        fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, entries: &[PausedProcess]) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.path, serde_json::to_string_pretty(entries)?)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,355 @@
+use crate::models::{BindAttempt, BindOutcome, DiagnosisCause, DiagnosisFinding, NetNamespace, PortDiagnosis};
+use super::ephemeral::EphemeralRange;
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+/// The addresses servers usually bind
+const PROBE_ADDRESSES: [IpAddr; 4] = [
+    IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+    IpAddr::V4(Ipv4Addr::LOCALHOST),
+    IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+    IpAddr::V6(Ipv6Addr::LOCALHOST),
+];
+
+/// Bind and listen on every probe address, closing each socket straight away.
+/// On Unix each address is tried with and without `SO_REUSEADDR`, which is what
+/// separates a live listener from leftover TIME_WAIT sockets.
+pub fn bind_attempts(port: u16) -> Vec<BindAttempt> {
+    let reuse_modes: &[bool] = if cfg!(unix) { &[true, false] } else { &[false] };
+    let mut attempts = Vec::new();
+
+    for ip in PROBE_ADDRESSES {
+        for &reuse_address in reuse_modes {
+            let address = SocketAddr::new(ip, port);
+            let (outcome, error) = match try_bind(address, reuse_address) {
+                Ok(()) => (BindOutcome::Bound, None),
+                Err(e) => {
+                    let outcome = match e.kind() {
+                        io::ErrorKind::AddrInUse => BindOutcome::InUse,
+                        io::ErrorKind::PermissionDenied => BindOutcome::PermissionDenied,
+                        io::ErrorKind::AddrNotAvailable => BindOutcome::AddressUnavailable,
+                        _ => BindOutcome::Failed,
+                    };
+                    (outcome, Some(e.to_string()))
+                }
+            };
+
+            attempts.push(BindAttempt {
+                address: address.to_string(),
+                reuse_address,
+                outcome,
+                error,
+            });
+        }
+    }
+
+    attempts
+}
+
+/// Whether a typical server could bind every address the host supports
+pub fn is_bindable(attempts: &[BindAttempt]) -> bool {
+    attempts
+        .iter()
+        .filter(|attempt| attempt.reuse_address == cfg!(unix))
+        .all(|attempt| matches!(attempt.outcome, BindOutcome::Bound | BindOutcome::AddressUnavailable))
+}
+
+/// The listening sockets of one process on the port
+pub fn listener(pid: u32, process_name: Option<String>, addresses: &[String]) -> DiagnosisFinding {
+    let owner = describe_owner(pid, process_name.as_deref());
+    let fixes = if pid > 0 {
+        vec![
+            format!("Stop {} if it shouldn't be running", owner),
+            "Or leave it and run the new server on another port".to_string(),
+        ]
+    } else {
+        vec!["Run Porter with sudo/root privileges to see the owner".to_string()]
+    };
+
+    DiagnosisFinding {
+        cause: DiagnosisCause::Listener,
+        blocking: true,
+        summary: format!("{} is listening on {}", owner, addresses.join(" and ")),
+        sockets: Some(addresses.len()),
+        pid: (pid > 0).then_some(pid),
+        process_name,
+        namespace: None,
+        fixes,
+    }
+}
+
+pub fn namespace_listener(
+    pid: u32,
+    process_name: Option<String>,
+    address: &str,
+    namespace: NetNamespace,
+) -> DiagnosisFinding {
+    let name = namespace
+        .name
+        .clone()
+        .unwrap_or_else(|| format!("net:[{}]", namespace.inode));
+
+    DiagnosisFinding {
+        cause: DiagnosisCause::NamespaceListener,
+        blocking: false,
+        summary: format!(
+            "{} is listening on {} inside network namespace {}. That doesn't conflict with the host, but host clients won't reach it without a published port.",
+            describe_owner(pid, process_name.as_deref()),
+            address,
+            name
+        ),
+        sockets: Some(1),
+        pid: (pid > 0).then_some(pid),
+        process_name,
+        namespace: Some(namespace),
+        fixes: Vec::new(),
+    }
+}
+
+pub fn time_wait(count: usize, longest_ms: u64) -> DiagnosisFinding {
+    let seconds = longest_ms.div_ceil(1000);
+
+    DiagnosisFinding {
+        cause: DiagnosisCause::TimeWait,
+        blocking: true,
+        summary: format!(
+            "{} closed connection(s) on this port are in TIME_WAIT for up to {} s more. Servers that don't set SO_REUSEADDR can't bind until they expire.",
+            count, seconds
+        ),
+        sockets: Some(count),
+        pid: None,
+        process_name: None,
+        namespace: None,
+        fixes: vec![
+            "Set SO_REUSEADDR before binding; most frameworks do this by default".to_string(),
+            format!("Or wait about {} s", seconds),
+        ],
+    }
+}
+
+pub fn close_wait(pid: u32, process_name: Option<String>, count: usize) -> DiagnosisFinding {
+    let owner = describe_owner(pid, process_name.as_deref());
+
+    DiagnosisFinding {
+        cause: DiagnosisCause::CloseWait,
+        blocking: true,
+        summary: format!(
+            "{} holds {} connection(s) in CLOSE_WAIT: the peers hung up but it never closed its side",
+            owner, count
+        ),
+        sockets: Some(count),
+        pid: (pid > 0).then_some(pid),
+        process_name,
+        namespace: None,
+        fixes: vec![
+            format!("Restart {} to release them", owner),
+            "It is probably leaking sockets; make sure it closes connections the peer closes".to_string(),
+        ],
+    }
+}
+
+pub fn ephemeral_client(pid: u32, process_name: Option<String>, remotes: &[String], port: u16) -> DiagnosisFinding {
+    let owner = describe_owner(pid, process_name.as_deref());
+
+    DiagnosisFinding {
+        cause: DiagnosisCause::EphemeralClient,
+        blocking: true,
+        summary: format!(
+            "{} has an outgoing connection to {} that the kernel gave this port as its source port",
+            owner,
+            remotes.join(", ")
+        ),
+        sockets: Some(remotes.len()),
+        pid: (pid > 0).then_some(pid),
+        process_name,
+        namespace: None,
+        fixes: vec![
+            format!("Close that connection or restart {}", owner),
+            format!("Reserve the port so it isn't handed out again: `porter reserve {}`", port),
+        ],
+    }
+}
+
+pub fn privileged(port: u16, unprivileged_start: u16) -> DiagnosisFinding {
+    DiagnosisFinding {
+        cause: DiagnosisCause::Privileged,
+        blocking: true,
+        summary: format!(
+            "Ports below {} can only be bound by root or with CAP_NET_BIND_SERVICE",
+            unprivileged_start
+        ),
+        sockets: None,
+        pid: None,
+        process_name: None,
+        namespace: None,
+        fixes: vec![
+            "Grant the server binary the capability: `setcap 'cap_net_bind_service=+ep' <binary>`".to_string(),
+            format!("Or lower `net.ipv4.ip_unprivileged_port_start` to {}", port),
+            format!("Or use a port at or above {}", unprivileged_start),
+        ],
+    }
+}
+
+pub fn ephemeral_range(port: u16, range: &EphemeralRange) -> Option<DiagnosisFinding> {
+    let bounds = range.range()?;
+    if !range.contains(port) {
+        return None;
+    }
+
+    Some(DiagnosisFinding {
+        cause: DiagnosisCause::EphemeralRange,
+        blocking: false,
+        summary: format!(
+            "Port is inside the ephemeral range {}-{}, so any outgoing connection can take it while the server is down",
+            bounds.start, bounds.end
+        ),
+        sockets: None,
+        pid: None,
+        process_name: None,
+        namespace: None,
+        fixes: vec![
+            format!("Reserve it: `porter reserve {}`", port),
+            format!("Or move the service outside {}-{}", bounds.start, bounds.end),
+        ],
+    })
+}
+
+pub fn reserved() -> DiagnosisFinding {
+    DiagnosisFinding {
+        cause: DiagnosisCause::Reserved,
+        blocking: false,
+        summary: "Port is reserved, so the kernel won't give it to outgoing connections".to_string(),
+        sockets: None,
+        pid: None,
+        process_name: None,
+        namespace: None,
+        fixes: Vec::new(),
+    }
+}
+
+/// Order findings most likely cause first and sum them up in one sentence
+pub fn conclude(
+    port: u16,
+    mut findings: Vec<DiagnosisFinding>,
+    bind_attempts: Vec<BindAttempt>,
+    alternatives: Vec<u16>,
+) -> PortDiagnosis {
+    findings.sort_by_key(|finding| (!finding.blocking, finding.cause));
+
+    let bindable = is_bindable(&bind_attempts);
+    let first_blocking = findings.iter().find(|finding| finding.blocking);
+
+    let summary = match (bindable, first_blocking) {
+        (true, None) => format!("Port {} is free to bind.", port),
+        (true, Some(finding)) => format!("Port {} can be bound right now, but: {}", port, finding.summary),
+        (false, Some(finding)) => format!("Port {} can't be bound: {}", port, finding.summary),
+        (false, None) => {
+            let error = bind_attempts
+                .iter()
+                .find_map(|attempt| attempt.error.clone())
+                .unwrap_or_else(|| "unknown error".to_string());
+            format!(
+                "Port {} can't be bound ({}) and Porter found no socket holding it. The owner may be in a namespace Porter can't read, or a security policy may be refusing the bind.",
+                port, error
+            )
+        }
+    };
+
+    PortDiagnosis {
+        port,
+        bindable,
+        summary,
+        findings,
+        bind_attempts,
+        alternatives,
+    }
+}
+
+fn describe_owner(pid: u32, process_name: Option<&str>) -> String {
+    match (pid, process_name) {
+        (0, _) => "A process Porter can't see".to_string(),
+        (pid, Some(name)) => format!("{} (PID {})", name, pid),
+        (pid, None) => format!("PID {}", pid),
+    }
+}
+
+#[cfg(unix)]
+fn try_bind(address: SocketAddr, reuse_address: bool) -> io::Result<()> {
+    use std::mem::size_of;
+    use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+
+    let family = if address.is_ipv4() { libc::AF_INET } else { libc::AF_INET6 };
+    let fd = unsafe { libc::socket(family, libc::SOCK_STREAM, 0) };
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let socket = unsafe { OwnedFd::from_raw_fd(fd) };
+
+    if reuse_address {
+        let enabled: libc::c_int = 1;
+        unsafe {
+            libc::setsockopt(
+                socket.as_raw_fd(),
+                libc::SOL_SOCKET,
+                libc::SO_REUSEADDR,
+                &enabled as *const _ as *const libc::c_void,
+                size_of::<libc::c_int>() as libc::socklen_t,
+            );
+        }
+    }
+
+    let (storage, length) = sockaddr(address);
+    if unsafe { libc::bind(socket.as_raw_fd(), &storage as *const _ as *const libc::sockaddr, length) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    // Some conflicts, e.g. with SO_REUSEPORT groups, only surface at listen
+    if unsafe { libc::listen(socket.as_raw_fd(), 1) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(())
+}
+
+#[cfg(unix)]
+fn sockaddr(address: SocketAddr) -> (libc::sockaddr_storage, libc::socklen_t) {
+    use std::mem::size_of;
+
+    let mut storage: libc::sockaddr_storage = unsafe { std::mem::zeroed() };
+    let length = match address {
+        SocketAddr::V4(v4) => {
+            let sin = unsafe { &mut *(&mut storage as *mut _ as *mut libc::sockaddr_in) };
+            sin.sin_family = libc::AF_INET as libc::sa_family_t;
+            sin.sin_port = v4.port().to_be();
+            sin.sin_addr = libc::in_addr {
+                s_addr: u32::from(*v4.ip()).to_be(),
+            };
+            #[cfg(target_os = "macos")]
+            {
+                sin.sin_len = size_of::<libc::sockaddr_in>() as u8;
+            }
+            size_of::<libc::sockaddr_in>()
+        }
+        SocketAddr::V6(v6) => {
+            let sin6 = unsafe { &mut *(&mut storage as *mut _ as *mut libc::sockaddr_in6) };
+            sin6.sin6_family = libc::AF_INET6 as libc::sa_family_t;
+            sin6.sin6_port = v6.port().to_be();
+            sin6.sin6_addr = libc::in6_addr {
+                s6_addr: v6.ip().octets(),
+            };
+            #[cfg(target_os = "macos")]
+            {
+                sin6.sin6_len = size_of::<libc::sockaddr_in6>() as u8;
+            }
+            size_of::<libc::sockaddr_in6>()
+        }
+    };
+
+    (storage, length as libc::socklen_t)
+}
+
+/// Windows sockets already refuse to share a port unless asked to
+#[cfg(not(unix))]
+fn try_bind(address: SocketAddr, _reuse_address: bool) -> io::Result<()> {
+    std::net::TcpListener::bind(address).map(drop)
+}
@@ -1,81 +1,732 @@
-use crate::models::{Port, PortStatus, Process, Protocol};
-use crate::platform;
+use crate::error::PorterError;
+use crate::models::{
+    AttributionFailure, BacklogReport, ConnectionDetails, Exposure, ExposureEntry, ExposureReport,
+    InterfacePorts, KillImpact, ListenCounters, ListenerBacklog, OtherSocket, Port,
+    PortDiagnosis, PortStatus, Process, ProcessAncestry, ProcessSocketUsage, ProcessSockets, Protocol,
+    RestartManager, ServiceGraph, SocketLeakReport, SocketUsageSample, UnixSocket, UserPorts,
+};
+use crate::platform::{self, NetworkConnection, TcpDiag, UnixSocketEntry};
+use super::ephemeral::EphemeralRange;
+use super::{
+    ProcFs, UserResolver, containers, exposure, interfaces, kill_impact, leaks, port_diagnosis, process_tree,
+    service_graph, systemd, users,
+};
 use anyhow::Result;
 use sysinfo::{Pid, System};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::path::Path;
+
+/// Accept-queue fill ratio at which a listener is flagged
+const BACKLOG_WARNING_RATIO: f32 = 0.8;
+/// Listen counter samples kept for the overflow history
+const LISTEN_HISTORY_LEN: usize = 120;
+/// Per-process socket usage samples kept for leak trends
+const SOCKET_HISTORY_LEN: usize = 120;
+/// Common developer ports, also the default set of service ports
+const COMMON_PORTS: [u16; 20] = [
+    3000, 3001, 4200, 5000, 5173, 8000, 8080, 8888, 9000, 9090,
+    80, 443, 5432, 3306, 6379, 27017, 5672, 15672, 11211, 5984
+];
+/// Alternatives suggested for a service port inside the ephemeral range
+const ALTERNATIVE_COUNT: usize = 3;
 
 pub struct PortMonitor {
     system: System,
+    users: UserResolver,
+    /// Also read the socket tables of other network namespaces (Linux only)
+    scan_namespaces: bool,
+    listen_history: VecDeque<ListenCounters>,
+    /// Ports services are configured to use, checked for ephemeral conflicts
+    service_ports: HashSet<u16>,
+    /// Socket usage samples per process, keyed by PID and start time to survive PID reuse
+    socket_history: HashMap<(u32, i64), VecDeque<SocketUsageSample>>,
 }
 
 impl PortMonitor {
     pub fn new() -> Self {
         Self {
             system: System::new_all(),
+            users: UserResolver::new(),
+            scan_namespaces: false,
+            listen_history: VecDeque::new(),
+            service_ports: COMMON_PORTS.into_iter().collect(),
+            socket_history: HashMap::new(),
         }
     }
 
+    pub fn set_scan_namespaces(&mut self, enabled: bool) {
+        self.scan_namespaces = enabled;
+    }
+
+    /// Ports services are configured to use, checked on top of the common ones
+    pub fn set_service_ports(&mut self, ports: &[u16]) {
+        self.service_ports = COMMON_PORTS.iter().chain(ports).copied().collect();
+    }
+
     /// Get all active ports (listening or established)
     pub fn get_active_ports(&mut self) -> Result<Vec<Port>> {
+        let service_ports = self.service_ports.clone();
+        self.active_ports(&service_ports)
+    }
+
+    fn active_ports(&mut self, service_ports: &HashSet<u16>) -> Result<Vec<Port>> {
         self.system.refresh_all();
 
         // Use platform-specific implementation
-        let port_info = platform::get_network_connections()?;
+        let port_info = if self.scan_namespaces {
+            platform::get_namespaced_connections()?
+        } else {
+            platform::get_network_connections()?
+        };
 
-        let mut ports: HashMap<u16, Port> = HashMap::new();
+        // Outgoing client sockets sitting on a port nothing listens on
+        let host_conns = || {
+            port_info
+                .iter()
+                .filter(|conn| conn.namespace.as_ref().is_none_or(|ns| ns.host))
+        };
+        let listening: HashSet<u16> = host_conns()
+            .filter(|conn| conn.is_listening())
+            .map(|conn| conn.local_port)
+            .collect();
+        let client_held: HashSet<u16> = host_conns()
+            .filter(|conn| !conn.is_listening() && conn.remote_port != 0)
+            .map(|conn| conn.local_port)
+            .filter(|port| !listening.contains(port))
+            .collect();
+
+        // The same port number can be bound independently in every namespace,
+        // so key by namespace too; the host namespace keys as `None`
+        let mut ports: HashMap<(Option<u64>, u16), Port> = HashMap::new();
 
         for conn in port_info {
-            if ports.contains_key(&conn.local_port) {
+            let key = (
+                conn.namespace.as_ref().filter(|ns| !ns.host).map(|ns| ns.inode),
+                conn.local_port,
+            );
+
+            // Prefer a socket we can attribute over one we cannot
+            if let Some(existing) = ports.get(&key) {
+                if existing.process.is_some() || conn.pid == 0 {
+                    continue;
+                }
+            }
+
+            let port = self.port_from_connection(conn);
+            ports.insert(key, port);
+        }
+
+        // Containers published without a userland proxy have no listening socket on the host
+        for published in containers::published_ports() {
+            ports
+                .entry((None, published.host_port))
+                .or_insert_with(|| Port {
+                    port: published.host_port,
+                    status: PortStatus::Occupied,
+                    protocol: published.protocol,
+                    process: None,
+                    ip_address: published.host_ip,
+                    created_at: Some(chrono::Utc::now()),
+                    unattributed: None,
+                    container: None,
+                    namespace: None,
+                    in_ephemeral_range: false,
+                    ephemeral_conflict: false,
+                    alternatives: Vec::new(),
+                })
+                .container = Some(published.container);
+        }
+
+        let range = EphemeralRange::read(&ProcFs::new());
+        let in_use: HashSet<u16> = ports.values().map(|port| port.port).collect();
+        for port in ports.values_mut() {
+            port.ephemeral_conflict =
+                service_ports.contains(&port.port) && client_held.contains(&port.port);
+            mark_ephemeral(port, &range, &in_use, service_ports);
+        }
+
+        Ok(ports.into_values().collect())
+    }
+
+    fn port_from_connection(&mut self, conn: NetworkConnection) -> Port {
+        let process = if conn.pid > 0 {
+            self.get_process_info(conn.pid)
+        } else {
+            None
+        };
+
+        let status = if process.is_some() {
+            if platform::is_system_process(conn.pid) {
+                PortStatus::System
+            } else {
+                PortStatus::Occupied
+            }
+        } else {
+            match conn.unattributed {
+                Some(reason) if reason != AttributionFailure::NoOwner => PortStatus::Unknown,
+                _ => PortStatus::Free,
+            }
+        };
+
+        Port {
+            port: conn.local_port,
+            status,
+            protocol: conn.protocol,
+            process,
+            ip_address: conn.local_address,
+            created_at: Some(chrono::Utc::now()),
+            unattributed: conn.unattributed,
+            container: None,
+            namespace: conn.namespace,
+            in_ephemeral_range: false,
+            ephemeral_conflict: false,
+            alternatives: Vec::new(),
+        }
+    }
+
+    /// Group listeners by the interface they are reachable on
+    pub fn get_ports_by_interface(&mut self) -> Result<Vec<InterfacePorts>> {
+        self.system.refresh_all();
+
+        let interfaces = interfaces::network_interfaces();
+        let diags = platform::tcp_diag_by_inode();
+        let v6_only_default = interfaces::v6_only_default();
+        let mut groups: Vec<Vec<Port>> = vec![Vec::new(); interfaces.len()];
+        let mut unmatched = Vec::new();
+
+        // Not de-duplicated by port, so 127.0.0.1:8080 and 0.0.0.0:8080 land where they belong
+        for conn in platform::get_network_connections()?.into_iter().filter(|conn| conn.is_listening()) {
+            // UDP sockets, and TCP ones without netlink, fall back to the system default
+            let v6_only = conn
+                .tcp
+                .as_ref()
+                .and_then(|tcp| diags.get(&tcp.inode)?.v6_only)
+                .unwrap_or(v6_only_default);
+            let reachable: Vec<usize> = interfaces::reachable_on(&conn.local_address, v6_only, &interfaces)
+                .into_iter()
+                .filter_map(|iface| interfaces.iter().position(|other| other.name == iface.name))
+                .collect();
+            let port = self.port_from_connection(conn);
+
+            if reachable.is_empty() {
+                unmatched.push(port);
                 continue;
             }
+            for index in reachable {
+                groups[index].push(port.clone());
+            }
+        }
+
+        let mut result: Vec<InterfacePorts> = interfaces
+            .into_iter()
+            .zip(groups)
+            .map(|(interface, mut ports)| {
+                ports.sort_by_key(|port| port.port);
+                InterfacePorts {
+                    interface: Some(interface),
+                    ports,
+                }
+            })
+            .collect();
+
+        if !unmatched.is_empty() {
+            unmatched.sort_by_key(|port| port.port);
+            result.push(InterfacePorts {
+                interface: None,
+                ports: unmatched,
+            });
+        }
+
+        Ok(result)
+    }
+
+    /// Get active ports owned by a user, matched by name or numeric UID
+    pub fn get_ports_by_user(&mut self, user: &str) -> Result<Vec<Port>> {
+        let ports = self.get_active_ports()?;
+
+        Ok(ports
+            .into_iter()
+            .filter(|port| {
+                port.process.as_ref().is_some_and(|process| {
+                    process.user.as_deref() == Some(user)
+                        || process.uid.map(|uid| uid.to_string()).as_deref() == Some(user)
+                })
+            })
+            .collect())
+    }
+
+    /// Group active ports by owning user; unattributed ports end up in a group without a user
+    pub fn get_ports_grouped_by_user(&mut self) -> Result<Vec<UserPorts>> {
+        let mut groups: HashMap<(Option<String>, Option<u32>), Vec<Port>> = HashMap::new();
+
+        for port in self.get_active_ports()? {
+            let key = port
+                .process
+                .as_ref()
+                .map(|process| (process.user.clone(), process.uid))
+                .unwrap_or((None, None));
+            groups.entry(key).or_default().push(port);
+        }
+
+        let mut result: Vec<UserPorts> = groups
+            .into_iter()
+            .map(|((user, uid), mut ports)| {
+                ports.sort_by_key(|port| port.port);
+                UserPorts { user, uid, ports }
+            })
+            .collect();
+        result.sort_by(|a, b| a.user.is_none().cmp(&b.user.is_none()).then_with(|| a.user.cmp(&b.user)));
+
+        Ok(result)
+    }
 
+    /// Get detailed information about a specific port
+    pub fn get_port_details(&mut self, port: u16) -> Result<Option<Port>> {
+        let ports = self.get_active_ports()?;
+        Ok(ports.into_iter().find(|p| p.port == port))
+    }
+
+    /// Get every socket on a local port, with queue, timer and `tcp_info`
+    /// details where the backend has them
+    pub fn get_connection_details(&self, port: u16) -> Result<Vec<ConnectionDetails>> {
+        let connections = platform::get_network_connections()?;
+        let diags = platform::tcp_diag_by_inode();
+
+        Ok(connections
+            .into_iter()
+            .filter(|conn| conn.local_port == port)
+            .map(|conn| connection_details(conn, &diags))
+            .collect())
+    }
+
+    /// Every listener, connection and Unix socket `pid` holds, optionally
+    /// followed by the same for each of its descendants
+    pub fn get_process_sockets(&mut self, pid: u32, include_descendants: bool) -> Result<ProcessSockets> {
+        self.system.refresh_all();
+
+        let process = self.get_process_info(pid).ok_or(PorterError::ProcessNotFound { pid })?;
+        let connections = platform::get_network_connections()?;
+        let diags = platform::tcp_diag_by_inode();
+        // Not available on every platform; TCP sockets are still worth showing
+        let unix_entries = platform::get_unix_sockets().unwrap_or_default();
+
+        let mut result = sockets_of(process, &connections, &unix_entries, &diags);
+        if include_descendants {
+            for child in process_tree::descendants(&self.system, pid) {
+                if let Some(process) = self.get_process_info(child.pid) {
+                    result
+                        .descendants
+                        .push(sockets_of(process, &connections, &unix_entries, &diags));
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Get the chain of processes that started `pid`, up to init, and the
+    /// terminal, IDE or supervisor it was launched from
+    pub fn get_process_ancestry(&mut self, pid: u32) -> Result<ProcessAncestry> {
+        self.system.refresh_all();
+
+        let process = self.get_process_info(pid).ok_or(PorterError::ProcessNotFound { pid })?;
+        let ancestors = process_tree::ancestors(&self.system, pid);
+
+        Ok(ProcessAncestry {
+            process,
+            launched_by: process_tree::launched_by(&ancestors),
+            ancestors,
+        })
+    }
+
+    /// Get the accept-queue saturation of every listener and sample the
+    /// system-wide listen overflow counters
+    pub fn get_backlog_report(&mut self) -> Result<BacklogReport> {
+        self.system.refresh_all();
+
+        let connections = platform::get_network_connections()?;
+        let diags = platform::tcp_diag_by_inode();
+        let mut listeners = Vec::new();
+
+        for conn in connections.iter().filter(|conn| conn.is_listening()) {
+            let Some(tcp) = &conn.tcp else {
+                continue;
+            };
+
+            let max_backlog = diags.get(&tcp.inode).and_then(|diag| diag.max_backlog);
+            // listen(fd, 0) still lets one connection queue
+            let saturation = max_backlog.map(|max| tcp.rx_queue as f32 / max.max(1) as f32);
             let process = if conn.pid > 0 {
                 self.get_process_info(conn.pid)
             } else {
                 None
             };
 
-            let status = if process.is_some() {
-                if platform::is_system_process(conn.pid) {
-                    PortStatus::System
-                } else {
-                    PortStatus::Occupied
-                }
-            } else {
-                PortStatus::Free
-            };
+            listeners.push(ListenerBacklog {
+                port: conn.local_port,
+                local_address: conn.local_address.clone(),
+                process,
+                queued: tcp.rx_queue,
+                max_backlog,
+                saturation,
+                filling: saturation.is_some_and(|ratio| ratio >= BACKLOG_WARNING_RATIO),
+            });
+        }
+
+        // Fullest queues first
+        listeners.sort_by(|a, b| {
+            b.saturation
+                .unwrap_or(0.0)
+                .total_cmp(&a.saturation.unwrap_or(0.0))
+                .then(b.queued.cmp(&a.queued))
+        });
 
-            ports.insert(conn.local_port, Port {
+        let previous = self.listen_history.back().map(|sample| sample.listen_overflows);
+        if let Some((listen_overflows, listen_drops)) = platform::listen_counters() {
+            if self.listen_history.len() == LISTEN_HISTORY_LEN {
+                self.listen_history.pop_front();
+            }
+            self.listen_history.push_back(ListenCounters {
+                timestamp: chrono::Utc::now(),
+                listen_overflows,
+                listen_drops,
+            });
+        }
+
+        let counters: Vec<ListenCounters> = self.listen_history.iter().cloned().collect();
+        let recent_overflows = previous
+            .zip(counters.last())
+            .map(|(previous, current)| current.listen_overflows.saturating_sub(previous));
+
+        Ok(BacklogReport {
+            listeners,
+            counters,
+            recent_overflows,
+        })
+    }
+
+    /// Classify every listener by who can reach it and flag sensitive services
+    /// bound beyond loopback
+    pub fn get_exposure_report(&mut self) -> Result<ExposureReport> {
+        self.system.refresh_all();
+
+        let interfaces = interfaces::network_interfaces();
+        let mut listeners = Vec::new();
+
+        // Not de-duplicated by port: 127.0.0.1:5432 next to [::]:5432 is exactly what to catch
+        for conn in platform::get_network_connections()?.into_iter().filter(|conn| conn.is_listening()) {
+            let process_name = self
+                .system
+                .process(Pid::from_u32(conn.pid))
+                .filter(|_| conn.pid > 0)
+                .map(|process| process.name().to_string_lossy().to_string());
+            let (reach, interface) = exposure::classify(&conn.local_address, &interfaces);
+            let service = exposure::sensitive_service(conn.local_port, process_name.as_deref());
+
+            listeners.push(ExposureEntry {
                 port: conn.local_port,
-                status,
                 protocol: conn.protocol,
+                local_address: conn.local_address,
+                exposure: reach,
+                interface,
+                flagged: service.is_some() && reach != Exposure::LoopbackOnly,
+                service,
+                pid: (conn.pid > 0).then_some(conn.pid),
+                process_name,
+            });
+        }
+
+        exposure::sort_entries(&mut listeners);
+
+        Ok(ExposureReport {
+            generated_at: chrono::Utc::now(),
+            hostname: System::host_name().unwrap_or_else(|| "Unknown".to_string()),
+            interfaces: interfaces.into_iter().flat_map(|iface| iface.addresses).collect(),
+            listeners,
+        })
+    }
+
+    /// Count each process's TCP sockets by state and its open descriptors,
+    /// and flag the ones trending toward a leak across calls
+    pub fn get_socket_leak_report(&mut self) -> Result<SocketLeakReport> {
+        self.system.refresh_all();
+
+        let proc = ProcFs::new();
+        let now = chrono::Utc::now();
+
+        let mut states: BTreeMap<u32, BTreeMap<String, usize>> = BTreeMap::new();
+        for conn in platform::get_network_connections()?.into_iter().filter(|conn| conn.pid > 0) {
+            *states.entry(conn.pid).or_default().entry(conn.state).or_default() += 1;
+        }
+
+        let mut processes = Vec::new();
+        let mut seen = HashSet::new();
+        for (pid, sockets_by_state) in states {
+            let Some(process) = self.get_process_info(pid) else {
+                continue;
+            };
+            let key = (pid, process.started_at.timestamp());
+            seen.insert(key);
+
+            let close_wait = sockets_by_state.get("CLOSE_WAIT").copied().unwrap_or(0);
+            let open_fds = proc.open_fds(pid);
+            let (fd_limit, _) = proc.process_file_limits(&pid.to_string());
+            let fd_usage = open_fds
+                .zip(fd_limit.filter(|&limit| limit > 0))
+                .map(|(open, limit)| open as f32 / limit as f32);
+
+            let samples = self.socket_history.entry(key).or_default();
+            if samples.len() == SOCKET_HISTORY_LEN {
+                samples.pop_front();
+            }
+            samples.push_back(SocketUsageSample {
+                timestamp: now,
+                close_wait,
+                open_fds,
+            });
+            let history: Vec<SocketUsageSample> = samples.iter().cloned().collect();
+
+            processes.push(ProcessSocketUsage {
                 process,
-                ip_address: conn.local_address,
-                created_at: Some(chrono::Utc::now()),
+                sockets_by_state,
+                close_wait,
+                open_fds,
+                fd_limit,
+                fd_usage,
+                warnings: leaks::warnings(&history, fd_limit),
+                history,
             });
         }
 
-        Ok(ports.into_values().collect())
+        // Forget processes that exited or closed all their sockets
+        self.socket_history.retain(|key, _| seen.contains(key));
+        leaks::sort_usage(&mut processes);
+
+        Ok(SocketLeakReport {
+            generated_at: now,
+            processes,
+        })
     }
 
-    /// Get detailed information about a specific port
-    pub fn get_port_details(&mut self, port: u16) -> Result<Option<Port>> {
-        let ports = self.get_active_ports()?;
-        Ok(ports.into_iter().find(|p| p.port == port))
+    /// Explain why `port` can or cannot be bound: what holds it in any
+    /// namespace, kernel restrictions, and a live bind attempt per address
+    pub fn diagnose_port(&mut self, port: u16) -> Result<PortDiagnosis> {
+        self.system.refresh_all();
+
+        let proc = ProcFs::new();
+        let range = EphemeralRange::read(&proc);
+        let bind_attempts = port_diagnosis::bind_attempts(port);
+
+        let connections = platform::get_namespaced_connections()?;
+        let in_use: HashSet<u16> = connections.iter().map(|conn| conn.local_port).collect();
+        let (host, other): (Vec<NetworkConnection>, Vec<NetworkConnection>) = connections
+            .into_iter()
+            .filter(|conn| conn.local_port == port)
+            .partition(|conn| conn.namespace.as_ref().is_none_or(|ns| ns.host));
+
+        let process_name = |pid: u32| {
+            self.system
+                .process(Pid::from_u32(pid))
+                .filter(|_| pid > 0)
+                .map(|process| process.name().to_string_lossy().to_string())
+        };
+
+        let mut listeners: BTreeMap<u32, Vec<String>> = BTreeMap::new();
+        let mut close_wait: BTreeMap<u32, usize> = BTreeMap::new();
+        let mut clients: BTreeMap<u32, Vec<String>> = BTreeMap::new();
+        let mut time_wait = (0, 0);
+
+        for conn in &host {
+            match conn.state.as_str() {
+                "TIME_WAIT" => {
+                    let expires = conn.tcp.as_ref().map_or(0, |tcp| tcp.timer_expires_ms);
+                    time_wait = (time_wait.0 + 1, time_wait.1.max(expires));
+                }
+                "CLOSE_WAIT" => *close_wait.entry(conn.pid).or_default() += 1,
+                _ if conn.is_listening() => listeners
+                    .entry(conn.pid)
+                    .or_default()
+                    .push(format_address(&conn.local_address, port)),
+                _ if conn.remote_port != 0 => clients
+                    .entry(conn.pid)
+                    .or_default()
+                    .push(format_address(&conn.remote_address, conn.remote_port)),
+                _ => {}
+            }
+        }
+
+        let mut findings = Vec::new();
+        for (pid, addresses) in &listeners {
+            findings.push(port_diagnosis::listener(*pid, process_name(*pid), addresses));
+        }
+        for conn in other.iter().filter(|conn| conn.is_listening()) {
+            if let Some(namespace) = conn.namespace.clone() {
+                let address = format_address(&conn.local_address, port);
+                findings.push(port_diagnosis::namespace_listener(conn.pid, process_name(conn.pid), &address, namespace));
+            }
+        }
+        if time_wait.0 > 0 {
+            findings.push(port_diagnosis::time_wait(time_wait.0, time_wait.1));
+        }
+        for (pid, count) in close_wait {
+            findings.push(port_diagnosis::close_wait(pid, process_name(pid), count));
+        }
+        // With a listener present these are its accepted connections, not clients
+        if listeners.is_empty() {
+            for (pid, remotes) in &clients {
+                findings.push(port_diagnosis::ephemeral_client(*pid, process_name(*pid), remotes, port));
+            }
+        }
+        if let Some(start) = proc.unprivileged_port_start().filter(|&start| port < start) {
+            findings.push(port_diagnosis::privileged(port, start));
+        }
+        findings.extend(port_diagnosis::ephemeral_range(port, &range));
+        if proc.reserved_ports().iter().any(|reserved| reserved.contains(port)) {
+            findings.push(port_diagnosis::reserved());
+        }
+
+        let alternatives = range.alternatives(port, &in_use, ALTERNATIVE_COUNT);
+        Ok(port_diagnosis::conclude(port, findings, bind_attempts, alternatives))
+    }
+
+    /// Who depends on `pid`: clients of its listeners, its descendants, and
+    /// any manager that would start it again after a kill
+    pub fn get_kill_impact(&mut self, pid: u32) -> Result<KillImpact> {
+        self.system.refresh_all();
+
+        let process = self.get_process_info(pid).ok_or(PorterError::ProcessNotFound { pid })?;
+        let connections = platform::get_network_connections()?;
+
+        let listening_ports = kill_impact::listening_ports(&connections, pid);
+        let clients = kill_impact::clients(&connections, pid, &listening_ports, |pid| {
+            self.system
+                .process(Pid::from_u32(pid))
+                .map(|process| process.name().to_string_lossy().to_string())
+        });
+
+        let children = process_tree::descendants(&self.system, pid);
+
+        let mut restarted_by = Vec::new();
+        if let Some(unit) = process.systemd_unit.clone().filter(|unit| unit.managed) {
+            restarted_by.push(RestartManager::Systemd { unit });
+        }
+        let container = containers::container_for_pid(pid);
+        if container.is_some() || !listening_ports.is_empty() {
+            let published = containers::published_ports();
+            if let Some((runtime, id)) = container {
+                let name = published
+                    .iter()
+                    .find(|published| published.container.id == id)
+                    .map(|published| published.container.name.clone());
+                restarted_by.push(RestartManager::Container {
+                    runtime: runtime.to_string(),
+                    id,
+                    name,
+                });
+            } else if let Some(published) = connections
+                .iter()
+                .filter(|conn| kill_impact::held_by(conn, pid) && conn.is_listening())
+                .find_map(|conn| {
+                    containers::published_for(&published, conn.protocol, &conn.local_address, conn.local_port)
+                })
+            {
+                // docker-proxy or rootlessport forwarding a published port
+                restarted_by.push(RestartManager::Container {
+                    runtime: published.container.runtime.clone(),
+                    id: published.container.id.clone(),
+                    name: Some(published.container.name.clone()),
+                });
+            }
+        }
+
+        let summary = kill_impact::summarize(&process.name, pid, &clients, children.len(), &restarted_by);
+
+        Ok(KillImpact {
+            process,
+            listening_ports,
+            clients,
+            children,
+            restarted_by,
+            summary,
+        })
+    }
+
+    /// Which local processes hold connections to which local listeners
+    pub fn get_service_graph(&mut self) -> Result<ServiceGraph> {
+        self.system.refresh_all();
+
+        let connections = platform::get_network_connections()?;
+        let interfaces = interfaces::interface_addresses();
+
+        Ok(service_graph::build(&connections, &interfaces, |pid| {
+            self.system
+                .process(Pid::from_u32(pid))
+                .map(|process| process.name().to_string_lossy().to_string())
+        }))
+    }
+
+    /// Get Unix domain sockets, only listening ones unless `all` is set
+    pub fn get_unix_sockets(&mut self, all: bool) -> Result<Vec<UnixSocket>> {
+        self.system.refresh_all();
+
+        let mut sockets = Vec::new();
+        for entry in platform::get_unix_sockets()? {
+            if !all && !entry.listening {
+                continue;
+            }
+
+            let process = if entry.pid > 0 {
+                self.get_process_info(entry.pid)
+            } else {
+                None
+            };
+
+            sockets.push(unix_socket(entry, process));
+        }
+
+        Ok(sockets)
+    }
+
+    /// Get raw, ICMP, packet and SCTP sockets
+    pub fn get_other_sockets(&mut self) -> Result<Vec<OtherSocket>> {
+        self.system.refresh_all();
+
+        let mut sockets = Vec::new();
+        for entry in platform::get_other_sockets()? {
+            let process = if entry.pid > 0 {
+                self.get_process_info(entry.pid)
+            } else {
+                None
+            };
+
+            sockets.push(OtherSocket {
+                protocol: entry.protocol,
+                local_address: entry.local_address,
+                port: entry.port,
+                ip_protocol: entry.ip_protocol,
+                ethertype: entry.ethertype,
+                interface: entry.interface,
+                inode: entry.inode,
+                process,
+            });
+        }
+
+        Ok(sockets)
     }
 
     /// Scan common developer ports
     pub fn scan_common_ports(&mut self) -> Result<Vec<Port>> {
-        let common_ports = vec![
-            3000, 3001, 4200, 5000, 5173, 8000, 8080, 8888, 9000, 9090,
-            80, 443, 5432, 3306, 6379, 27017, 5672, 15672, 11211, 5984
-        ];
-        self.scan_ports(&common_ports)
+        self.scan_ports(&COMMON_PORTS)
     }
 
     /// Scan specific ports
     pub fn scan_ports(&mut self, ports_to_scan: &[u16]) -> Result<Vec<Port>> {
-        let all_ports = self.get_active_ports()?;
+        // Ports asked about explicitly count as service ports
+        let mut service_ports = self.service_ports.clone();
+        service_ports.extend(ports_to_scan);
+        let all_ports = self.active_ports(&service_ports)?;
+        let range = EphemeralRange::read(&ProcFs::new());
+        let in_use: HashSet<u16> = all_ports.iter().map(|port| port.port).collect();
 
         let mut result = Vec::new();
         for port_num in ports_to_scan {
@@ -83,14 +734,22 @@ impl PortMonitor {
                 result.push(port.clone());
             } else {
                 // Port is free
-                result.push(Port {
+                let mut port = Port {
                     port: *port_num,
                     status: PortStatus::Free,
                     protocol: Protocol::TCP,
                     process: None,
                     ip_address: "127.0.0.1".to_string(),
                     created_at: Some(chrono::Utc::now()),
-                });
+                    unattributed: None,
+                    container: None,
+                    namespace: None,
+                    in_ephemeral_range: false,
+                    ephemeral_conflict: false,
+                    alternatives: Vec::new(),
+                };
+                mark_ephemeral(&mut port, &range, &in_use, &service_ports);
+                result.push(port);
             }
         }
 
@@ -98,14 +757,17 @@ impl PortMonitor {
     }
 
     /// Get process information by PID
-    fn get_process_info(&self, pid: u32) -> Option<Process> {
+    fn get_process_info(&mut self, pid: u32) -> Option<Process> {
         let process = self.system.process(Pid::from_u32(pid))?;
+        let uid = process.user_id();
+        let gid = process.group_id();
 
         Some(Process {
             pid,
             name: process.name().to_str().unwrap_or("").to_string(),
             path: process.exe().map(|p| p.to_string_lossy().to_string()).unwrap_or_default(),
             command: process.cmd().iter().map(|s| s.to_string_lossy().to_string()).collect::<Vec<_>>().join(" "),
+            parent_pid: process.parent().map(|parent| parent.as_u32()),
             working_dir: process.cwd().map(|p| p.to_string_lossy().to_string()),
             cpu_usage: process.cpu_usage(),
             memory_usage: process.memory(),
@@ -113,7 +775,109 @@ impl PortMonitor {
                 process.start_time() as i64,
                 0
             ).unwrap_or_else(|| chrono::Utc::now()),
-            user: process.user_id().map(|uid| format!("{:?}", uid)),
+            user: uid.map(|uid| self.users.user_name(uid).unwrap_or_else(|| uid.to_string())),
+            uid: uid.and_then(users::numeric_uid),
+            group: gid.map(|gid| self.users.group_name(gid).unwrap_or_else(|| gid.to_string())),
+            gid: gid.map(|gid| *gid),
+            systemd_unit: systemd::unit_for_pid(pid),
+            namespace_pid: platform::namespace_pid(pid),
         })
     }
 }
+
+/// Flag ports the kernel may give to outgoing connections and, for service
+/// ports at risk, suggest ones it never will
+fn mark_ephemeral(port: &mut Port, range: &EphemeralRange, in_use: &HashSet<u16>, service_ports: &HashSet<u16>) {
+    port.in_ephemeral_range = range.contains(port.port);
+
+    let at_risk = port.ephemeral_conflict || (port.in_ephemeral_range && service_ports.contains(&port.port));
+    if at_risk {
+        port.alternatives = range.alternatives(port.port, in_use, ALTERNATIVE_COUNT);
+    }
+}
+
+/// `127.0.0.1:8080`, or `[::1]:8080` for IPv6
+fn format_address(address: &str, port: u16) -> String {
+    if address.contains(':') {
+        format!("[{}]:{}", address, port)
+    } else {
+        format!("{}:{}", address, port)
+    }
+}
+
+/// Attach netlink `tcp_info` to a TCP socket; sockets without TCP details get the basic fields
+fn connection_details(conn: NetworkConnection, diags: &HashMap<u64, TcpDiag>) -> ConnectionDetails {
+    let tcp = conn.tcp.map(|mut tcp| {
+        // TIME_WAIT sockets all report inode 0
+        if let Some(diag) = diags.get(&tcp.inode).filter(|_| tcp.inode != 0) {
+            tcp.info = diag.info.clone();
+            tcp.max_backlog = diag.max_backlog;
+        }
+        tcp
+    });
+
+    ConnectionDetails {
+        local_address: conn.local_address,
+        local_port: conn.local_port,
+        remote_address: conn.remote_address,
+        remote_port: conn.remote_port,
+        protocol: conn.protocol,
+        state: conn.state,
+        pid: conn.pid,
+        tcp,
+    }
+}
+
+fn unix_socket(entry: UnixSocketEntry, process: Option<Process>) -> UnixSocket {
+    // Abstract sockets (`@name`) have no file that could go missing
+    let stale = entry
+        .path
+        .as_deref()
+        .is_some_and(|path| !path.starts_with('@') && !Path::new(path).exists());
+
+    UnixSocket {
+        path: entry.path,
+        socket_type: entry.socket_type,
+        state: entry.state,
+        listening: entry.listening,
+        inode: entry.inode,
+        process,
+        stale,
+    }
+}
+
+/// `process`'s sockets from the scanned lists, listeners sorted by port. A socket
+/// inherited across `fork`, like a pre-fork server's listener, belongs to every holder.
+fn sockets_of(
+    process: Process,
+    connections: &[NetworkConnection],
+    unix_entries: &[UnixSocketEntry],
+    diags: &HashMap<u64, TcpDiag>,
+) -> ProcessSockets {
+    let pid = process.pid;
+    let (listening, owned): (Vec<NetworkConnection>, Vec<NetworkConnection>) = connections
+        .iter()
+        .filter(|conn| conn.pid == pid || conn.shared_with.contains(&pid))
+        .cloned()
+        .partition(|conn| conn.is_listening());
+
+    let mut listeners: Vec<ConnectionDetails> =
+        listening.into_iter().map(|conn| connection_details(conn, diags)).collect();
+    let mut others: Vec<ConnectionDetails> = owned.into_iter().map(|conn| connection_details(conn, diags)).collect();
+    listeners.sort_by_key(|details| details.local_port);
+    others.sort_by_key(|details| (details.local_port, details.remote_port));
+
+    let unix_sockets = unix_entries
+        .iter()
+        .filter(|entry| entry.pid == pid || entry.shared_with.contains(&pid))
+        .map(|entry| unix_socket(entry.clone(), None))
+        .collect();
+
+    ProcessSockets {
+        process,
+        listeners,
+        connections: others,
+        unix_sockets,
+        descendants: Vec::new(),
+    }
+}
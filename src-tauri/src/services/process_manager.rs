@@ -1,23 +1,49 @@
+use crate::error::PorterError;
+use crate::models::{
+    AuditEntry, AuditTarget, LaunchSpec, PauseMethod, PausedProcess, RestartOptions, RestartResult,
+};
 use crate::platform;
-use anyhow::{Result, anyhow};
-use sysinfo::{Pid, System};
+use super::containers;
+use super::systemd::{self, UnitAction};
+use super::{AuditLog, PauseStore, UserResolver, admin, users};
+use anyhow::Result;
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
 
 #[cfg(not(target_os = "windows"))]
 use sysinfo::Signal;
 
 pub struct ProcessManager {
     system: System,
+    pause_store: PauseStore,
+    audit_log: AuditLog,
+    users: UserResolver,
 }
 
 impl ProcessManager {
     pub fn new() -> Self {
         Self {
             system: System::new_all(),
+            pause_store: PauseStore::new(),
+            audit_log: AuditLog::new(),
+            users: UserResolver::new(),
         }
     }
 
     /// Kill a process by PID
     pub fn kill_process(&mut self, pid: u32) -> Result<bool> {
+        self.audited("kill_process", Some(pid), None, |manager, signals| {
+            manager.terminate(pid, signals)
+        })
+    }
+
+    /// Terminate a process, recording every signal sent
+    fn terminate(&mut self, pid: u32, signals: &mut Vec<String>) -> Result<bool> {
+        refuse_protected(pid)?;
+
         self.system.refresh_processes(sysinfo::ProcessesToUpdate::All);
 
         let pid_obj = Pid::from_u32(pid);
@@ -27,31 +53,29 @@ impl ProcessManager {
             #[cfg(target_os = "windows")]
             {
                 // Windows: use kill() directly (sends SIGKILL equivalent)
-                if process.kill() {
+                if !process.kill() {
+                    signals.push("TerminateProcess (failed)".to_string());
+                } else {
+                    signals.push("TerminateProcess".to_string());
                     std::thread::sleep(std::time::Duration::from_millis(300));
                     self.system.refresh_processes(sysinfo::ProcessesToUpdate::All);
 
                     if self.system.process(pid_obj).is_none() {
                         return Ok(true);
                     }
-
-                    // Still running? This might need admin privileges
-                    return Err(anyhow!(
-                        "Failed to kill process (PID: {}). This process may require administrator privileges to terminate.",
-                        pid
-                    ));
-                } else {
-                    return Err(anyhow!(
-                        "Access denied when trying to kill process (PID: {}). You may need to run Porter as administrator.",
-                        pid
-                    ));
                 }
+
+                // Access denied or still running? This might need admin privileges
+                return Err(PorterError::permission_denied(pid, "kill").into());
             }
 
             #[cfg(not(target_os = "windows"))]
             {
                 // Unix: Try SIGTERM first, then SIGKILL
-                if process.kill_with(Signal::Term).unwrap_or(false) {
+                if !process.kill_with(Signal::Term).unwrap_or(false) {
+                    signals.push("SIGTERM (failed)".to_string());
+                } else {
+                    signals.push("SIGTERM".to_string());
                     std::thread::sleep(std::time::Duration::from_millis(500));
                     self.system.refresh_processes(sysinfo::ProcessesToUpdate::All);
 
@@ -62,31 +86,680 @@ impl ProcessManager {
                     // Force kill if still running
                     if let Some(process) = self.system.process(pid_obj) {
                         if process.kill() {
+                            signals.push("SIGKILL".to_string());
                             return Ok(true);
                         }
+                        signals.push("SIGKILL (failed)".to_string());
                     }
                 }
 
-                return Err(anyhow!(
-                    "Failed to kill process (PID: {}). You may need sudo/root privileges.",
-                    pid
-                ));
+                return Err(PorterError::permission_denied(pid, "kill").into());
+            }
+        }
+
+        Err(PorterError::ProcessNotFound { pid }.into())
+    }
+
+    /// Kill a process owned by another user through the privileged helper (Linux only)
+    pub fn kill_process_privileged(&mut self, pid: u32, port: u16) -> Result<bool> {
+        self.audited("kill_process_privileged", Some(pid), Some(port), |manager, signals| {
+            manager.terminate_privileged(pid, port, signals)
+        })
+    }
+
+    fn terminate_privileged(&mut self, pid: u32, port: u16, signals: &mut Vec<String>) -> Result<bool> {
+        let pid_obj = Pid::from_u32(pid);
+
+        let sent = admin::privileged_signal(pid, "TERM", port);
+        signals.push(format!("SIGTERM (pkexec){}", if sent.is_ok() { "" } else { ", failed" }));
+        sent?;
+        std::thread::sleep(Duration::from_millis(500));
+        self.system.refresh_processes(ProcessesToUpdate::All);
+
+        if self.system.process(pid_obj).is_none() {
+            return Ok(true);
+        }
+
+        // polkit keeps the authorization briefly, so this does not prompt again
+        let sent = admin::privileged_signal(pid, "KILL", port);
+        signals.push(format!("SIGKILL (pkexec){}", if sent.is_ok() { "" } else { ", failed" }));
+        sent?;
+        std::thread::sleep(Duration::from_millis(300));
+        self.system.refresh_processes(ProcessesToUpdate::All);
+
+        // Usually stuck in uninterruptible sleep (D state)
+        if self.system.process(pid_obj).is_some() {
+            return Err(PorterError::Timeout {
+                operation: format!("PID {} to exit after SIGKILL", pid),
+                timeout_ms: 300,
             }
+            .into());
         }
 
-        Err(anyhow!("Process not found: PID {}", pid))
+        Ok(true)
     }
 
     /// Kill process by port number
     pub fn kill_process_by_port(&mut self, port: u16) -> Result<bool> {
+        // Killing docker-proxy or rootlessport would not free the port, so stop the container instead
+        let published = containers::published_ports();
+        let sockets: Vec<_> = platform::get_network_connections()?
+            .into_iter()
+            .filter(|conn| conn.local_port == port && conn.is_listening())
+            .collect();
+        let container = if sockets.is_empty() {
+            // Published without a userland proxy, so nothing on the host holds the port
+            published.iter().find(|published| published.host_port == port)
+        } else {
+            sockets.iter().find_map(|conn| {
+                containers::published_for(&published, conn.protocol, &conn.local_address, port)
+            })
+        };
+        if let Some(published) = container {
+            return self.stop_container(&published.container.id, Some(port));
+        }
+
+        let pid = find_pid_by_port(port);
+        self.audited("kill_process_by_port", pid.as_ref().ok().copied(), Some(port), |manager, signals| {
+            manager.terminate(pid?, signals)
+        })
+    }
+
+    /// Kill the process listening on a Unix domain socket path
+    pub fn kill_process_by_socket(&mut self, path: &str) -> Result<bool> {
+        let pid = find_pid_by_socket(path);
+        self.audited("kill_process_by_socket", pid.as_ref().ok().copied(), None, |manager, signals| {
+            manager.terminate(pid?, signals)
+        })
+    }
+
+    /// Stop or restart the systemd service a process runs under, so systemd does not
+    /// simply restart it the way it would after a raw kill
+    pub fn manage_systemd_unit(&mut self, pid: u32, action: UnitAction) -> Result<bool> {
+        let unit = systemd::unit_for_pid(pid).ok_or_else(|| PorterError::InvalidInput {
+            detail: format!("PID {} does not belong to a systemd unit", pid),
+        })?;
+
+        // Scopes such as session-2.scope hold a whole login; stopping one logs the user out
+        if !unit.managed || systemd::is_protected_unit(&unit.name) {
+            return Err(PorterError::Protected {
+                pid,
+                reason: format!("{} is not a service Porter may {}", unit.name, action.verb()),
+            }
+            .into());
+        }
+        let operation = format!("{}_systemd_unit", action.verb());
+
+        self.audited(&operation, Some(pid), None, |_, signals| {
+            signals.push(format!("systemd {} {}", action.verb(), unit.name));
+            systemd::manage_unit(&unit, action)?;
+            Ok(true)
+        })
+    }
+
+    /// Stop a container through its runtime's API
+    pub fn stop_container(&mut self, id: &str, port: Option<u16>) -> Result<bool> {
+        self.audited("stop_container", None, port, |_, signals| {
+            signals.push(format!("container stop {}", id));
+            containers::stop_container(id)?;
+            Ok(true)
+        })
+    }
+
+    /// Recent audit log entries, newest first
+    pub fn audit_entries(&self, limit: Option<usize>) -> Result<Vec<AuditEntry>> {
+        self.audit_log.entries(limit)
+    }
+
+    /// Run a destructive action and record the attempt in the audit log
+    fn audited(
+        &mut self,
+        action: &str,
+        pid: Option<u32>,
+        port: Option<u16>,
+        run: impl FnOnce(&mut Self, &mut Vec<String>) -> Result<bool>,
+    ) -> Result<bool> {
+        // An action that cannot be recorded is not taken
+        self.audit_log
+            .check_writable()
+            .map_err(|e| PorterError::backend_unavailable("Audit log", e))?;
+
+        // Capture the target before it is gone
+        let target = pid.and_then(|pid| self.audit_target(pid));
+        let mut signals = Vec::new();
+        let result = run(self, &mut signals);
+
+        let entry = AuditEntry {
+            timestamp: chrono::Utc::now(),
+            user: users::current_user(),
+            action: action.to_string(),
+            pid,
+            target,
+            port,
+            signals,
+            success: matches!(result, Ok(true)),
+            error: result.as_ref().err().map(|e| e.to_string()),
+        };
+
+        if let Err(e) = self.audit_log.record(&entry) {
+            // Still report what happened to the target alongside the logging failure
+            let outcome = match &result {
+                Ok(true) => "succeeded".to_string(),
+                Ok(false) => "had no effect".to_string(),
+                Err(error) => format!("failed ({})", error),
+            };
+            return Err(PorterError::Internal {
+                detail: format!("{} {}, but the audit log could not be written: {}", action, outcome, e),
+            }
+            .into());
+        }
+
+        result
+    }
+
+    fn audit_target(&mut self, pid: u32) -> Option<AuditTarget> {
+        let pid_obj = Pid::from_u32(pid);
+        self.system.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&[pid_obj]),
+            ProcessRefreshKind::everything(),
+        );
+        let process = self.system.process(pid_obj)?;
+
+        Some(AuditTarget {
+            pid,
+            name: process.name().to_string_lossy().to_string(),
+            command: process
+                .cmd()
+                .iter()
+                .map(|s| s.to_string_lossy().to_string())
+                .collect::<Vec<_>>()
+                .join(" "),
+            started_at: chrono::DateTime::from_timestamp(process.start_time() as i64, 0)
+                .unwrap_or_else(chrono::Utc::now),
+            uid: process.user_id().and_then(users::numeric_uid),
+            user: process.user_id().and_then(|uid| self.users.user_name(uid)),
+        })
+    }
+
+    /// Capture the command line, working directory and environment of a process
+    pub fn capture_launch_spec(&mut self, pid: u32) -> Result<LaunchSpec> {
+        let pid_obj = Pid::from_u32(pid);
+        self.system.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&[pid_obj]),
+            ProcessRefreshKind::everything(),
+        );
+
+        let process = self
+            .system
+            .process(pid_obj)
+            .ok_or(PorterError::ProcessNotFound { pid })?;
+
+        let working_dir = process.cwd().map(|p| p.to_string_lossy().to_string());
+        let mut cmd = process
+            .cmd()
+            .iter()
+            .map(|s| s.to_string_lossy().to_string())
+            .collect::<Vec<_>>();
+
+        if cmd.is_empty() {
+            match process.exe() {
+                Some(exe) => cmd.push(exe.to_string_lossy().to_string()),
+                None => return Err(PorterError::permission_denied(pid, "inspect").into()),
+            }
+        }
+
+        let env = process
+            .environ()
+            .iter()
+            .filter_map(|entry| {
+                let entry = entry.to_string_lossy();
+                entry
+                    .split_once('=')
+                    .map(|(key, value)| (key.to_string(), value.to_string()))
+            })
+            .collect::<HashMap<_, _>>();
+
+        let program = resolve_program(&cmd[0], working_dir.as_deref());
+        let uid = process.user_id().and_then(users::numeric_uid);
+
+        Ok(LaunchSpec {
+            pid,
+            program,
+            args: cmd[1..].to_vec(),
+            working_dir,
+            env,
+            uid,
+            gid: uid.and(process.group_id().map(|gid| *gid)),
+        })
+    }
+
+    /// Capture the launch spec of whatever process owns a port
+    pub fn launch_spec_for_port(&mut self, port: u16) -> Result<LaunchSpec> {
+        let pid = find_pid_by_port(port)?;
+        self.capture_launch_spec(pid)
+    }
+
+    /// Kill the owner of a port and relaunch it detached once the port is free
+    pub fn restart_port_owner(&mut self, port: u16, options: RestartOptions) -> Result<RestartResult> {
+        let timeout = restart_timeout(&options);
+        let spec = self.stop_port_owner(port, options)?;
+        relaunch(port, spec, timeout)
+    }
+
+    /// First half of a restart: capture the owner's launch spec, apply the edits
+    /// and kill every process holding the port. Finish with `relaunch`.
+    pub fn stop_port_owner(&mut self, port: u16, options: RestartOptions) -> Result<LaunchSpec> {
+        let owners = self.port_owners(port)?;
+        let old_pid = owners[0];
+        let mut spec = self.capture_launch_spec(old_pid)?;
+
+        // Without the environment the service would come back configured differently
+        if spec.env.is_empty() {
+            return Err(PorterError::permission_denied(old_pid, "read the environment of").into());
+        }
+        // Only root can start a process as someone else
+        #[cfg(unix)]
+        if let Some(uid) = spec.uid {
+            let euid = unsafe { libc::geteuid() };
+            if uid != euid && euid != 0 {
+                return Err(PorterError::permission_denied(old_pid, "relaunch another user's").into());
+            }
+        }
+
+        if let Some(command) = options.command {
+            let (program, args) = command
+                .split_first()
+                .ok_or_else(|| PorterError::InvalidInput {
+                    detail: "Replacement command must not be empty".to_string(),
+                })?;
+            spec.program = program.clone();
+            spec.args = args.to_vec();
+        }
+        if options.working_dir.is_some() {
+            spec.working_dir = options.working_dir;
+        }
+        for key in &options.unset_env {
+            spec.env.remove(key);
+        }
+        spec.env.extend(options.env);
+
+        self.audited("restart_port_owner", Some(old_pid), Some(port), |manager, signals| {
+            for &pid in &owners {
+                let mut sent = Vec::new();
+                let result = manager.terminate(pid, &mut sent);
+                signals.extend(sent.into_iter().map(|signal| {
+                    if pid == old_pid {
+                        signal
+                    } else {
+                        format!("{} (PID {})", signal, pid)
+                    }
+                }));
+
+                match result {
+                    // Workers usually exit along with their master
+                    Err(e) if matches!(e.downcast_ref(), Some(PorterError::ProcessNotFound { .. })) => {}
+                    Err(e) => return Err(e),
+                    Ok(_) => {}
+                }
+            }
+            Ok(true)
+        })?;
+
+        Ok(spec)
+    }
+
+    /// Every process holding a port, topmost first: with a pre-forking server
+    /// the master comes before the workers it forked
+    fn port_owners(&mut self, port: u16) -> Result<Vec<u32>> {
+        let mut owners = find_pids_by_port(port)?;
+        self.system.refresh_processes(ProcessesToUpdate::All);
+
+        let parent_is_owner = |pid: &u32| {
+            self.system
+                .process(Pid::from_u32(*pid))
+                .and_then(|process| process.parent())
+                .is_some_and(|parent| owners.contains(&parent.as_u32()))
+        };
+        let (mut roots, children): (Vec<u32>, Vec<u32>) =
+            owners.iter().partition(|pid| !parent_is_owner(pid));
+        roots.extend(children);
+        owners = roots;
+
+        Ok(owners)
+    }
+
+    /// Pause a process without terminating it, remembering it so it can be resumed later
+    pub fn pause_process(&mut self, pid: u32, method: PauseMethod) -> Result<PausedProcess> {
+        // A stopped Porter could never send the matching resume
+        refuse_protected(pid)?;
+        self.system.refresh_processes(ProcessesToUpdate::All);
+
+        let process = self
+            .system
+            .process(Pid::from_u32(pid))
+            .ok_or(PorterError::ProcessNotFound { pid })?;
+
+        let name = process.name().to_string_lossy().to_string();
+        let started_at = chrono::DateTime::from_timestamp(process.start_time() as i64, 0)
+            .unwrap_or_else(chrono::Utc::now);
+
+        let cgroup = match method {
+            PauseMethod::Signal => {
+                send_pause_signal(process, true)?;
+                None
+            }
+            PauseMethod::CgroupFreeze => Some(freeze_cgroup(pid)?),
+        };
+
+        let mut entries = self.pause_store.load();
+        entries.retain(|entry| entry.pid != pid);
+        entries.push(PausedProcess {
+            pid,
+            name,
+            method,
+            paused_at: chrono::Utc::now(),
+            started_at,
+            cgroup,
+            listening_ports: Vec::new(),
+            warning: None,
+        });
+        self.pause_store.save(&entries)?;
+
+        self.paused_processes()?
+            .into_iter()
+            .find(|entry| entry.pid == pid)
+            .ok_or_else(|| PorterError::ProcessNotFound { pid }.into())
+    }
+
+    /// Resume a process previously paused by Porter (or any stopped process)
+    pub fn resume_process(&mut self, pid: u32) -> Result<bool> {
+        self.system.refresh_processes(ProcessesToUpdate::All);
+
+        let mut entries = self.pause_store.load();
+        let tracked = entries.iter().position(|entry| entry.pid == pid).map(|i| entries.remove(i));
+
+        match tracked.as_ref().and_then(|entry| entry.cgroup.as_deref()) {
+            Some(cgroup) => thaw_cgroup(pid, cgroup)?,
+            None => {
+                let process = self
+                    .system
+                    .process(Pid::from_u32(pid))
+                    .ok_or(PorterError::ProcessNotFound { pid })?;
+                send_pause_signal(process, false)?;
+            }
+        }
+
+        self.pause_store.save(&entries)?;
+        Ok(true)
+    }
+
+    /// Processes Porter has paused that are still alive, with the ports they still listen on
+    pub fn paused_processes(&mut self) -> Result<Vec<PausedProcess>> {
+        self.system.refresh_processes(ProcessesToUpdate::All);
+
+        let stored = self.pause_store.load();
+        let stored_count = stored.len();
+
+        // Drop entries whose process exited or whose PID now belongs to someone else
+        let mut entries: Vec<PausedProcess> = stored
+            .into_iter()
+            .filter(|entry| {
+                self.system
+                    .process(Pid::from_u32(entry.pid))
+                    .map(|p| p.start_time() as i64 == entry.started_at.timestamp())
+                    .unwrap_or(false)
+            })
+            .collect();
+
+        if entries.len() != stored_count {
+            self.pause_store.save(&entries)?;
+        }
+
         let connections = platform::get_network_connections()?;
 
-        for conn in connections {
-            if conn.local_port == port && conn.pid > 0 {
-                return self.kill_process(conn.pid);
+        for entry in &mut entries {
+            let mut ports: Vec<u16> = connections
+                .iter()
+                .filter(|conn| conn.pid == entry.pid && conn.is_listening())
+                .map(|conn| conn.local_port)
+                .collect();
+            ports.sort_unstable();
+            ports.dedup();
+
+            entry.warning = (!ports.is_empty()).then(|| {
+                format!(
+                    "Paused while still listening on port(s) {}; clients will hang instead of being refused",
+                    ports.iter().map(|p| p.to_string()).collect::<Vec<_>>().join(", ")
+                )
+            });
+            entry.listening_ports = ports;
+        }
+
+        Ok(entries)
+    }
+}
+
+/// Send SIGSTOP (pause) or SIGCONT (resume) to a process
+#[cfg(not(target_os = "windows"))]
+fn send_pause_signal(process: &sysinfo::Process, pause: bool) -> Result<()> {
+    let signal = if pause { Signal::Stop } else { Signal::Continue };
+
+    if process.kill_with(signal).unwrap_or(false) {
+        Ok(())
+    } else {
+        let operation = if pause { "pause" } else { "resume" };
+        Err(PorterError::permission_denied(process.pid().as_u32(), operation).into())
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn send_pause_signal(_process: &sysinfo::Process, _pause: bool) -> Result<()> {
+    Err(PorterError::unsupported("Pausing processes").into())
+}
+
+/// Freeze the cgroup a process belongs to, returning its path
+#[cfg(target_os = "linux")]
+fn freeze_cgroup(pid: u32) -> Result<String> {
+    let cgroup = platform::cgroup_path(pid)
+        .ok_or_else(|| {
+            PorterError::backend_unavailable("cgroup v2", format!("PID {} is not in a cgroup v2 hierarchy", pid))
+        })?;
+
+    if let Some(reason) = unfreezable_reason(&cgroup, platform::cgroup_path(std::process::id()).as_deref()) {
+        return Err(PorterError::Protected { pid, reason }.into());
+    }
+
+    platform::set_cgroup_frozen(&cgroup, true)
+        .map_err(|_| PorterError::permission_denied(pid, "freeze the cgroup of"))?;
+
+    Ok(cgroup)
+}
+
+/// Only a leaf service or scope that doesn't contain Porter may be frozen;
+/// anything above that takes the session, the desktop or Porter itself with it
+#[cfg(target_os = "linux")]
+fn unfreezable_reason(cgroup: &str, own: Option<&str>) -> Option<String> {
+    let Some(own) = own else {
+        return Some("Porter's own cgroup could not be determined".to_string());
+    };
+
+    let target: Vec<&str> = cgroup.split('/').filter(|part| !part.is_empty()).collect();
+    let ours: Vec<&str> = own.split('/').filter(|part| !part.is_empty()).collect();
+    if ours.starts_with(&target) {
+        return Some(format!("cgroup {} contains Porter itself", cgroup));
+    }
+
+    let leaf = target.last().copied().unwrap_or_default();
+    if !(leaf.ends_with(".service") || leaf.ends_with(".scope")) {
+        return Some(format!("{} is not a service or scope", cgroup));
+    }
+    if leaf == "init.scope" || (leaf.starts_with("session-") && leaf.ends_with(".scope")) {
+        return Some(format!("freezing {} would freeze a whole login session or init", cgroup));
+    }
+
+    None
+}
+
+#[cfg(target_os = "linux")]
+fn thaw_cgroup(pid: u32, cgroup: &str) -> Result<()> {
+    platform::set_cgroup_frozen(cgroup, false)
+        .map_err(|_| PorterError::permission_denied(pid, "thaw the cgroup of").into())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn freeze_cgroup(_pid: u32) -> Result<String> {
+    Err(PorterError::unsupported("Cgroup freezing").into())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn thaw_cgroup(_pid: u32, _cgroup: &str) -> Result<()> {
+    Err(PorterError::unsupported("Cgroup freezing").into())
+}
+
+/// Find the PID of the first process holding a port
+fn find_pid_by_port(port: u16) -> Result<u32> {
+    platform::get_network_connections()?
+        .into_iter()
+        .find(|conn| conn.local_port == port && conn.pid > 0)
+        .map(|conn| conn.pid)
+        .ok_or_else(|| PorterError::PortNotInUse { port }.into())
+}
+
+/// Every distinct process holding a socket on the port, lowest PID first
+fn find_pids_by_port(port: u16) -> Result<Vec<u32>> {
+    let pids: BTreeSet<u32> = platform::get_network_connections()?
+        .into_iter()
+        .filter(|conn| conn.local_port == port && conn.pid > 0)
+        .flat_map(|conn| std::iter::once(conn.pid).chain(conn.shared_with))
+        .collect();
+
+    if pids.is_empty() {
+        return Err(PorterError::PortNotInUse { port }.into());
+    }
+    Ok(pids.into_iter().collect())
+}
+
+fn find_pid_by_socket(path: &str) -> Result<u32> {
+    platform::get_unix_sockets()?
+        .into_iter()
+        .find(|socket| socket.listening && socket.pid > 0 && socket.path.as_deref() == Some(path))
+        .map(|socket| socket.pid)
+        .ok_or_else(|| PorterError::SocketNotInUse { path: path.to_string() }.into())
+}
+
+/// Poll until no process holds the port anymore
+fn wait_for_port_free(port: u16, timeout: Duration) -> Result<()> {
+    let deadline = Instant::now() + timeout;
+
+    while find_pid_by_port(port).is_ok() {
+        if Instant::now() >= deadline {
+            return Err(PorterError::Timeout {
+                operation: format!("port {} to be released", port),
+                timeout_ms: timeout.as_millis() as u64,
             }
+            .into());
         }
+        std::thread::sleep(Duration::from_millis(200));
+    }
+
+    Ok(())
+}
 
-        Err(anyhow!("Port {} is not in use", port))
+/// Relative program paths are relative to the original working directory
+fn resolve_program(program: &str, working_dir: Option<&str>) -> String {
+    let path = Path::new(program);
+
+    match working_dir {
+        Some(dir) if path.is_relative() && path.components().count() > 1 => {
+            Path::new(dir).join(path).to_string_lossy().to_string()
+        }
+        _ => program.to_string(),
     }
 }
+
+/// Porter itself and init, which no action may signal
+fn refuse_protected(pid: u32) -> Result<()> {
+    let reason = if pid == std::process::id() {
+        "this is Porter itself"
+    } else if pid == 1 {
+        "this is init"
+    } else {
+        return Ok(());
+    };
+
+    Err(PorterError::Protected {
+        pid,
+        reason: reason.to_string(),
+    }
+    .into())
+}
+
+/// Second half of a restart: wait for the port to be released, then start the
+/// process again. Needs no `ProcessManager`, so callers can release its lock first.
+pub fn relaunch(port: u16, spec: LaunchSpec, timeout: Duration) -> Result<RestartResult> {
+    let old_pid = spec.pid;
+    wait_for_port_free(port, timeout)?;
+
+    let new_pid = spawn_detached(&spec).map_err(|e| PorterError::RelaunchFailed {
+        pid: old_pid,
+        program: spec.program.clone(),
+        reason: e.to_string(),
+    })?;
+
+    Ok(RestartResult {
+        port,
+        old_pid,
+        new_pid,
+        spec,
+    })
+}
+
+pub fn restart_timeout(options: &RestartOptions) -> Duration {
+    Duration::from_millis(options.timeout_ms.unwrap_or(10_000))
+}
+
+/// Start a process detached from Porter so it outlives the app
+fn spawn_detached(spec: &LaunchSpec) -> std::io::Result<u32> {
+    let mut command = Command::new(&spec.program);
+    command
+        .args(&spec.args)
+        .env_clear()
+        .envs(&spec.env)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+
+    if let Some(dir) = &spec.working_dir {
+        command.current_dir(dir);
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        // DETACHED_PROCESS | CREATE_NEW_PROCESS_GROUP
+        command.creation_flags(0x00000008 | 0x00000200);
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+
+        // Drop to the original owner when an elevated Porter restarts someone else's service
+        if let (Some(uid), Some(gid)) = (spec.uid, spec.gid) {
+            if uid != unsafe { libc::geteuid() } {
+                command.uid(uid).gid(gid);
+            }
+        }
+    }
+
+    let mut child = command.spawn()?;
+    let pid = child.id();
+
+    // Reap the child when it exits so it does not linger as a zombie
+    std::thread::spawn(move || {
+        let _ = child.wait();
+    });
+
+    Ok(pid)
+}
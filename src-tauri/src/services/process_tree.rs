@@ -0,0 +1,190 @@
+use crate::models::{AncestorProcess, ChildProcess, Launcher, LauncherKind};
+use std::collections::{HashMap, HashSet, VecDeque};
+use sysinfo::{Pid, Process, System};
+
+/// Every descendant of `pid`, breadth first
+pub fn descendants(system: &System, pid: u32) -> Vec<ChildProcess> {
+    let mut children_of: HashMap<u32, Vec<u32>> = HashMap::new();
+    for (child, process) in system.processes() {
+        // sysinfo lists Linux threads as processes too
+        if process.thread_kind().is_some() {
+            continue;
+        }
+        if let Some(parent) = process.parent() {
+            children_of.entry(parent.as_u32()).or_default().push(child.as_u32());
+        }
+    }
+
+    let mut found = Vec::new();
+    let mut queue = VecDeque::from([pid]);
+    while let Some(parent) = queue.pop_front() {
+        let mut children = children_of.remove(&parent).unwrap_or_default();
+        children.sort_unstable();
+
+        for child in children {
+            let Some(process) = system.process(Pid::from_u32(child)) else {
+                continue;
+            };
+            found.push(ChildProcess {
+                pid: child,
+                parent_pid: parent,
+                name: process.name().to_string_lossy().to_string(),
+                command: command_line(process),
+            });
+            queue.push_back(child);
+        }
+    }
+
+    found
+}
+
+/// Parents of `pid` up to init, nearest first
+pub fn ancestors(system: &System, pid: u32) -> Vec<AncestorProcess> {
+    let mut found = Vec::new();
+    let mut seen = HashSet::from([pid]);
+    let mut current = system.process(Pid::from_u32(pid)).and_then(|process| process.parent());
+
+    while let Some(parent) = current {
+        // Guards against a PID being reused by a process in its own old chain
+        if !seen.insert(parent.as_u32()) {
+            break;
+        }
+        let Some(process) = system.process(parent) else {
+            break;
+        };
+
+        let name = process.name().to_string_lossy().to_string();
+        let command = command_line(process);
+        found.push(AncestorProcess {
+            pid: parent.as_u32(),
+            launcher: launcher(parent.as_u32(), &name, &command),
+            name,
+            command,
+            working_dir: process.cwd().map(|cwd| cwd.to_string_lossy().to_string()),
+        });
+        current = process.parent();
+    }
+
+    found
+}
+
+/// Nearest launcher that isn't a shell or init, since those say little about
+/// where a command was typed; otherwise the nearest of those
+pub fn launched_by(ancestors: &[AncestorProcess]) -> Option<Launcher> {
+    let launchers = || ancestors.iter().filter_map(|ancestor| ancestor.launcher.as_ref());
+    launchers()
+        .find(|launcher| !matches!(launcher.kind, LauncherKind::Shell | LauncherKind::Init))
+        .or_else(|| launchers().next())
+        .cloned()
+}
+
+const LAUNCHERS: &[(&str, LauncherKind, &str)] = &[
+    ("code", LauncherKind::Ide, "VS Code"),
+    ("code-insiders", LauncherKind::Ide, "VS Code Insiders"),
+    ("codium", LauncherKind::Ide, "VSCodium"),
+    ("cursor", LauncherKind::Ide, "Cursor"),
+    ("zed", LauncherKind::Ide, "Zed"),
+    ("zed-editor", LauncherKind::Ide, "Zed"),
+    ("idea", LauncherKind::Ide, "IntelliJ IDEA"),
+    ("pycharm", LauncherKind::Ide, "PyCharm"),
+    ("webstorm", LauncherKind::Ide, "WebStorm"),
+    ("goland", LauncherKind::Ide, "GoLand"),
+    ("clion", LauncherKind::Ide, "CLion"),
+    ("rustrover", LauncherKind::Ide, "RustRover"),
+    ("nvim", LauncherKind::Ide, "Neovim"),
+    ("emacs", LauncherKind::Ide, "Emacs"),
+    ("gnome-terminal-server", LauncherKind::Terminal, "GNOME Terminal"),
+    ("kgx", LauncherKind::Terminal, "GNOME Console"),
+    ("konsole", LauncherKind::Terminal, "Konsole"),
+    ("xfce4-terminal", LauncherKind::Terminal, "Xfce Terminal"),
+    ("alacritty", LauncherKind::Terminal, "Alacritty"),
+    ("kitty", LauncherKind::Terminal, "kitty"),
+    ("wezterm-gui", LauncherKind::Terminal, "WezTerm"),
+    ("ghostty", LauncherKind::Terminal, "Ghostty"),
+    ("foot", LauncherKind::Terminal, "foot"),
+    ("terminator", LauncherKind::Terminal, "Terminator"),
+    ("tilix", LauncherKind::Terminal, "Tilix"),
+    ("xterm", LauncherKind::Terminal, "xterm"),
+    ("urxvt", LauncherKind::Terminal, "urxvt"),
+    ("terminal", LauncherKind::Terminal, "Terminal"),
+    ("iterm2", LauncherKind::Terminal, "iTerm2"),
+    ("warp", LauncherKind::Terminal, "Warp"),
+    ("windowsterminal", LauncherKind::Terminal, "Windows Terminal"),
+    ("tmux", LauncherKind::Multiplexer, "tmux"),
+    ("tmux: server", LauncherKind::Multiplexer, "tmux"),
+    ("screen", LauncherKind::Multiplexer, "screen"),
+    ("zellij", LauncherKind::Multiplexer, "Zellij"),
+    ("bash", LauncherKind::Shell, "bash"),
+    ("zsh", LauncherKind::Shell, "zsh"),
+    ("fish", LauncherKind::Shell, "fish"),
+    ("sh", LauncherKind::Shell, "sh"),
+    ("dash", LauncherKind::Shell, "dash"),
+    ("ksh", LauncherKind::Shell, "ksh"),
+    ("tcsh", LauncherKind::Shell, "tcsh"),
+    ("nu", LauncherKind::Shell, "nushell"),
+    ("pwsh", LauncherKind::Shell, "PowerShell"),
+    ("powershell", LauncherKind::Shell, "PowerShell"),
+    ("cmd", LauncherKind::Shell, "cmd"),
+    ("systemd", LauncherKind::Supervisor, "systemd"),
+    ("supervisord", LauncherKind::Supervisor, "supervisord"),
+    ("runsv", LauncherKind::Supervisor, "runit"),
+    ("s6-supervise", LauncherKind::Supervisor, "s6"),
+    ("launchd", LauncherKind::Supervisor, "launchd"),
+    ("containerd-shim", LauncherKind::Container, "containerd"),
+    ("conmon", LauncherKind::Container, "Podman"),
+    ("docker-init", LauncherKind::Container, "Docker"),
+    ("tini", LauncherKind::Container, "tini"),
+    ("dumb-init", LauncherKind::Container, "dumb-init"),
+    ("cron", LauncherKind::Scheduler, "cron"),
+    ("crond", LauncherKind::Scheduler, "cron"),
+    ("anacron", LauncherKind::Scheduler, "anacron"),
+    ("atd", LauncherKind::Scheduler, "at"),
+    ("sshd", LauncherKind::RemoteLogin, "SSH"),
+];
+
+/// Linux truncates process names to 15 bytes
+const MAX_NAME_LEN: usize = 15;
+
+fn launcher(pid: u32, name: &str, command: &str) -> Option<Launcher> {
+    if pid == 1 {
+        return Some(Launcher {
+            kind: LauncherKind::Init,
+            name: name.to_string(),
+        });
+    }
+
+    // Remote-development servers and PM2 run as plain `node`
+    if command.contains(".vscode-server") {
+        return Some(Launcher {
+            kind: LauncherKind::Ide,
+            name: "VS Code Server".to_string(),
+        });
+    }
+    if command.starts_with("PM2 ") {
+        return Some(Launcher {
+            kind: LauncherKind::Supervisor,
+            name: "PM2".to_string(),
+        });
+    }
+
+    // Login shells are named `-bash`
+    let name = name.trim_start_matches('-').trim_end_matches(".exe").to_lowercase();
+    LAUNCHERS
+        .iter()
+        .find(|(pattern, _, _)| {
+            *pattern == name || (name.len() == MAX_NAME_LEN && pattern.starts_with(name.as_str()))
+        })
+        .map(|(_, kind, display)| Launcher {
+            kind: *kind,
+            name: display.to_string(),
+        })
+}
+
+fn command_line(process: &Process) -> String {
+    process
+        .cmd()
+        .iter()
+        .map(|arg| arg.to_string_lossy().to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,309 @@
+use crate::models::{NetworkLimits, PortRange, SocketStats};
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// `net.ipv4.ip_local_reserved_ports`, which also covers IPv6
+pub const RESERVED_PORTS: &str = "sys/net/ipv4/ip_local_reserved_ports";
+
+/// Reads kernel settings relative to a proc root, which tests point at a
+/// fixture directory. Missing files read as `None`, which is also what other
+/// platforms get.
+pub struct ProcFs {
+    root: PathBuf,
+}
+
+impl ProcFs {
+    /// Always the real `/proc`, since reservations are written through it too
+    pub fn new() -> Self {
+        Self {
+            root: PathBuf::from("/proc"),
+        }
+    }
+
+    /// A fixture directory laid out like `/proc`
+    #[cfg(test)]
+    pub fn with_root(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn path(&self, relative: &str) -> PathBuf {
+        self.root.join(relative)
+    }
+
+    pub fn read(&self, relative: &str) -> Option<String> {
+        fs::read_to_string(self.path(relative)).ok()
+    }
+
+    /// Write a setting in one call, the way sysctl files expect
+    pub fn write(&self, relative: &str, contents: &str) -> io::Result<()> {
+        let mut file = OpenOptions::new().write(true).truncate(true).open(self.path(relative))?;
+        file.write_all(contents.as_bytes())
+    }
+
+    /// Whether a setting exists and this process could write it
+    pub fn is_writable(&self, relative: &str) -> bool {
+        OpenOptions::new().write(true).open(self.path(relative)).is_ok()
+    }
+
+    fn value<T: FromStr>(&self, relative: &str) -> Option<T> {
+        self.read(relative)?.trim().parse().ok()
+    }
+
+    pub fn ephemeral_port_range(&self) -> Option<PortRange> {
+        let content = self.read("sys/net/ipv4/ip_local_port_range")?;
+        let mut bounds = content.split_whitespace().map(|n| n.parse::<u16>().ok());
+        Some(PortRange {
+            start: bounds.next()??,
+            end: bounds.next()??,
+        })
+    }
+
+    /// Ports excluded from ephemeral allocation, e.g. `8080,9000-9010`
+    pub fn reserved_ports(&self) -> Vec<PortRange> {
+        self.read(RESERVED_PORTS)
+            .map(|content| parse_port_list(&content))
+            .unwrap_or_default()
+    }
+
+    /// `net.ipv4.ip_unprivileged_port_start`; lower ports need root
+    pub fn unprivileged_port_start(&self) -> Option<u16> {
+        self.value("sys/net/ipv4/ip_unprivileged_port_start")
+    }
+
+    /// `net.ipv6.bindv6only`, the `IPV6_V6ONLY` default for new sockets
+    pub fn bindv6only(&self) -> Option<bool> {
+        self.value::<u8>("sys/net/ipv6/bindv6only").map(|value| value != 0)
+    }
+
+    pub fn somaxconn(&self) -> Option<u32> {
+        self.value("sys/net/core/somaxconn")
+    }
+
+    pub fn uptime_secs(&self) -> Option<u64> {
+        let content = self.read("uptime")?;
+        let seconds: f64 = content.split_whitespace().next()?.parse().ok()?;
+        Some(seconds as u64)
+    }
+
+    /// Soft and hard `Max open files` of the current process
+    pub fn open_file_limits(&self) -> (Option<u64>, Option<u64>) {
+        self.process_file_limits("self")
+    }
+
+    /// Soft and hard `Max open files` of `process`, a PID or `self`
+    pub fn process_file_limits(&self, process: &str) -> (Option<u64>, Option<u64>) {
+        let Some(limits) = self.read(&format!("{}/limits", process)) else {
+            return (None, None);
+        };
+        let Some(line) = limits.lines().find(|line| line.starts_with("Max open files")) else {
+            return (None, None);
+        };
+
+        let mut values = line["Max open files".len()..]
+            .split_whitespace()
+            .map(|value| value.parse::<u64>().ok());
+        (values.next().flatten(), values.next().flatten())
+    }
+
+    /// Number of file descriptors `pid` has open, if they can be listed
+    pub fn open_fds(&self, pid: u32) -> Option<u64> {
+        let entries = fs::read_dir(self.path(&format!("{}/fd", pid))).ok()?;
+        Some(entries.count() as u64)
+    }
+
+    pub fn socket_stats(&self) -> Option<SocketStats> {
+        let mut content = self.read("net/sockstat")?;
+        if let Some(v6) = self.read("net/sockstat6") {
+            content.push_str(&v6);
+        }
+
+        // Lines look like `TCP: inuse 5 orphan 0 tw 2 alloc 7 mem 1`
+        let mut fields: HashMap<(String, String), u64> = HashMap::new();
+        for line in content.lines() {
+            let Some((section, rest)) = line.split_once(':') else {
+                continue;
+            };
+            let tokens: Vec<&str> = rest.split_whitespace().collect();
+            for pair in tokens.chunks(2) {
+                if let [name, value] = pair {
+                    if let Ok(value) = value.parse() {
+                        fields.insert((section.to_string(), name.to_string()), value);
+                    }
+                }
+            }
+        }
+
+        let get = |section: &str, name: &str| {
+            fields
+                .get(&(section.to_string(), name.to_string()))
+                .copied()
+                .unwrap_or(0)
+        };
+
+        Some(SocketStats {
+            sockets_used: get("sockets", "used"),
+            tcp_in_use: get("TCP", "inuse"),
+            tcp_orphaned: get("TCP", "orphan"),
+            tcp_time_wait: get("TCP", "tw"),
+            tcp_allocated: get("TCP", "alloc"),
+            udp_in_use: get("UDP", "inuse"),
+            raw_in_use: get("RAW", "inuse"),
+            tcp6_in_use: get("TCP6", "inuse"),
+            udp6_in_use: get("UDP6", "inuse"),
+        })
+    }
+
+    pub fn network_limits(&self) -> NetworkLimits {
+        let (fd_limit_soft, fd_limit_hard) = self.open_file_limits();
+
+        NetworkLimits {
+            ephemeral_port_range: self.ephemeral_port_range(),
+            reserved_ports: self.reserved_ports(),
+            somaxconn: self.somaxconn(),
+            tcp_tw_reuse: self.value("sys/net/ipv4/tcp_tw_reuse"),
+            tcp_fin_timeout: self.value("sys/net/ipv4/tcp_fin_timeout"),
+            tcp_max_tw_buckets: self.value("sys/net/ipv4/tcp_max_tw_buckets"),
+            file_max: self.value("sys/fs/file-max"),
+            files_open: self
+                .read("sys/fs/file-nr")
+                .and_then(|content| content.split_whitespace().next()?.parse().ok()),
+            fd_limit_soft,
+            fd_limit_hard,
+            sockets: self.socket_stats(),
+        }
+    }
+}
+
+/// Parse the kernel's comma-separated port list format
+pub fn parse_port_list(content: &str) -> Vec<PortRange> {
+    content
+        .trim()
+        .split(',')
+        .filter_map(|item| {
+            let item = item.trim();
+            let (start, end) = item.split_once('-').unwrap_or((item, item));
+            Some(PortRange {
+                start: start.trim().parse().ok()?,
+                end: end.trim().parse().ok()?,
+            })
+        })
+        .collect()
+}
+
+/// Render ranges in the kernel's port list format, the inverse of [`parse_port_list`]
+pub fn format_port_list(ranges: &[PortRange]) -> String {
+    ranges
+        .iter()
+        .map(|range| {
+            if range.start == range.end {
+                range.start.to_string()
+            } else {
+                format!("{}-{}", range.start, range.end)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOCKSTAT: &str = "sockets: used 412
+TCP: inuse 23 orphan 1 tw 7 alloc 31 mem 4
+UDP: inuse 9 mem 2
+UDPLITE: inuse 0
+RAW: inuse 1
+FRAG: inuse 0 memory 0
+";
+    const SOCKSTAT6: &str = "TCP6: inuse 5
+UDP6: inuse 3
+UDPLITE6: inuse 0
+RAW6: inuse 0
+FRAG6: inuse 0 memory 0
+";
+    const LIMITS: &str = "Limit                     Soft Limit           Hard Limit           Units
+Max cpu time              unlimited            unlimited            seconds
+Max open files            1024                 524288               files
+Max locked memory         8388608              8388608              bytes
+";
+
+    fn fixture(files: &[(&str, &str)]) -> tempfile::TempDir {
+        let root = tempfile::tempdir().unwrap();
+        for (relative, contents) in files {
+            let path = root.path().join(relative);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+        root
+    }
+
+    #[test]
+    fn reads_socket_stats_from_both_families() {
+        let root = fixture(&[("net/sockstat", SOCKSTAT), ("net/sockstat6", SOCKSTAT6)]);
+        let stats = ProcFs::with_root(root.path()).socket_stats().unwrap();
+
+        assert_eq!(stats.sockets_used, 412);
+        assert_eq!(stats.tcp_in_use, 23);
+        assert_eq!(stats.tcp_orphaned, 1);
+        assert_eq!(stats.tcp_time_wait, 7);
+        assert_eq!(stats.tcp_allocated, 31);
+        assert_eq!(stats.udp_in_use, 9);
+        assert_eq!(stats.raw_in_use, 1);
+        assert_eq!(stats.tcp6_in_use, 5);
+        assert_eq!(stats.udp6_in_use, 3);
+    }
+
+    #[test]
+    fn socket_stats_need_only_the_ipv4_file() {
+        let root = fixture(&[("net/sockstat", SOCKSTAT)]);
+        let stats = ProcFs::with_root(root.path()).socket_stats().unwrap();
+        assert_eq!(stats.tcp_in_use, 23);
+        assert_eq!(stats.tcp6_in_use, 0);
+
+        let empty = tempfile::tempdir().unwrap();
+        assert!(ProcFs::with_root(empty.path()).socket_stats().is_none());
+    }
+
+    #[test]
+    fn reads_soft_and_hard_open_file_limits() {
+        let root = fixture(&[("self/limits", LIMITS)]);
+        assert_eq!(ProcFs::with_root(root.path()).open_file_limits(), (Some(1024), Some(524288)));
+
+        let unlimited = fixture(&[(
+            "self/limits",
+            "Max open files            unlimited            unlimited            files\n",
+        )]);
+        assert_eq!(ProcFs::with_root(unlimited.path()).open_file_limits(), (None, None));
+    }
+
+    #[test]
+    fn collects_network_limits() {
+        let root = fixture(&[
+            ("sys/net/ipv4/ip_local_port_range", "32768\t60999\n"),
+            (RESERVED_PORTS, "8080,9000-9010\n"),
+            ("sys/net/core/somaxconn", "4096\n"),
+            ("sys/net/ipv4/tcp_tw_reuse", "2\n"),
+            ("sys/fs/file-nr", "9216\t0\t9223372036854775807\n"),
+            ("net/sockstat", SOCKSTAT),
+            ("net/sockstat6", SOCKSTAT6),
+            ("self/limits", LIMITS),
+        ]);
+        let limits = ProcFs::with_root(root.path()).network_limits();
+
+        assert_eq!(limits.ephemeral_port_range, Some(PortRange { start: 32768, end: 60999 }));
+        assert_eq!(
+            limits.reserved_ports,
+            [PortRange { start: 8080, end: 8080 }, PortRange { start: 9000, end: 9010 }]
+        );
+        assert_eq!(limits.somaxconn, Some(4096));
+        assert_eq!(limits.tcp_tw_reuse, Some(2));
+        assert_eq!(limits.tcp_fin_timeout, None);
+        assert_eq!(limits.files_open, Some(9216));
+        assert_eq!((limits.fd_limit_soft, limits.fd_limit_hard), (Some(1024), Some(524288)));
+        assert_eq!(limits.sockets.map(|sockets| sockets.tcp6_in_use), Some(5));
+    }
+}
@@ -0,0 +1,193 @@
+use crate::error::PorterError;
+use crate::models::{PortRange, PortReservations};
+use super::procfs::{self, ProcFs, RESERVED_PORTS};
+use anyhow::Result;
+use std::io;
+
+pub fn get_reservations(proc: &ProcFs) -> PortReservations {
+    PortReservations {
+        reserved: proc.reserved_ports(),
+        ephemeral_port_range: proc.ephemeral_port_range(),
+        writable: proc.is_writable(RESERVED_PORTS),
+    }
+}
+
+/// Add ports to the reserved list. Lasts until reboot; persisting it is
+/// left to `/etc/sysctl.d`.
+pub fn reserve_ports(proc: &ProcFs, ports: &[PortRange]) -> Result<PortReservations> {
+    validate(ports)?;
+    let mut reserved = proc.reserved_ports();
+    reserved.extend_from_slice(ports);
+    write_reserved(proc, &normalize(reserved))?;
+    Ok(get_reservations(proc))
+}
+
+/// Remove ports from the reserved list, splitting any range they fall inside
+pub fn release_ports(proc: &ProcFs, ports: &[PortRange]) -> Result<PortReservations> {
+    validate(ports)?;
+    let reserved = subtract(&normalize(proc.reserved_ports()), ports);
+    write_reserved(proc, &reserved)?;
+    Ok(get_reservations(proc))
+}
+
+fn validate(ports: &[PortRange]) -> Result<()> {
+    if ports.is_empty() {
+        return Err(PorterError::InvalidInput {
+            detail: "No ports given".to_string(),
+        }
+        .into());
+    }
+
+    if let Some(range) = ports.iter().find(|range| range.start == 0 || range.start > range.end) {
+        return Err(PorterError::InvalidInput {
+            detail: format!("Invalid port range: {}", procfs::format_port_list(&[*range])),
+        }
+        .into());
+    }
+
+    Ok(())
+}
+
+fn write_reserved(proc: &ProcFs, reserved: &[PortRange]) -> Result<()> {
+    // The kernel rejects an empty write; a newline clears the list
+    let contents = format!("{}\n", procfs::format_port_list(reserved));
+
+    proc.write(RESERVED_PORTS, &contents).map_err(|e| {
+        match e.kind() {
+            io::ErrorKind::NotFound => PorterError::unsupported("Reserving ports"),
+            io::ErrorKind::PermissionDenied => PorterError::PrivilegeRequired {
+                operation: "Changing reserved ports".to_string(),
+            },
+            _ => PorterError::Internal {
+                detail: format!("Failed to write {}: {}", proc.path(RESERVED_PORTS).display(), e),
+            },
+        }
+        .into()
+    })
+}
+
+/// Sort ranges and merge the ones that overlap or touch
+fn normalize(mut ranges: Vec<PortRange>) -> Vec<PortRange> {
+    ranges.sort_by_key(|range| range.start);
+
+    let mut merged: Vec<PortRange> = Vec::new();
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start as u32 <= last.end as u32 + 1 => {
+                last.end = last.end.max(range.end);
+            }
+            _ => merged.push(range),
+        }
+    }
+
+    merged
+}
+
+fn subtract(reserved: &[PortRange], removed: &[PortRange]) -> Vec<PortRange> {
+    let mut remaining = reserved.to_vec();
+
+    for cut in removed {
+        remaining = remaining
+            .into_iter()
+            .flat_map(|range| {
+                let mut pieces = Vec::new();
+                if range.end < cut.start || range.start > cut.end {
+                    pieces.push(range);
+                    return pieces;
+                }
+                if range.start < cut.start {
+                    pieces.push(PortRange { start: range.start, end: cut.start - 1 });
+                }
+                if range.end > cut.end {
+                    pieces.push(PortRange { start: cut.end + 1, end: range.end });
+                }
+                pieces
+            })
+            .collect();
+    }
+
+    remaining
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn range(start: u16, end: u16) -> PortRange {
+        PortRange { start, end }
+    }
+
+    /// A proc root holding only the reserved ports setting
+    fn fixture(contents: &str) -> (tempfile::TempDir, ProcFs) {
+        let root = tempfile::tempdir().unwrap();
+        let path = root.path().join(RESERVED_PORTS);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, contents).unwrap();
+        let proc = ProcFs::with_root(root.path());
+        (root, proc)
+    }
+
+    #[test]
+    fn merges_overlapping_and_touching_ranges() {
+        let merged = normalize(vec![
+            range(9000, 9010),
+            range(8080, 8080),
+            range(9011, 9020),
+            range(8081, 8081),
+            range(9005, 9006),
+        ]);
+        assert_eq!(merged, [range(8080, 8081), range(9000, 9020)]);
+
+        assert_eq!(normalize(vec![range(65535, 65535), range(65534, 65535)]), [range(65534, 65535)]);
+    }
+
+    #[test]
+    fn release_splits_a_range() {
+        assert_eq!(
+            subtract(&[range(9000, 9010)], &[range(9005, 9005)]),
+            [range(9000, 9004), range(9006, 9010)]
+        );
+        assert_eq!(subtract(&[range(9000, 9010)], &[range(8990, 9003)]), [range(9004, 9010)]);
+        assert_eq!(subtract(&[range(9000, 9010), range(9500, 9500)], &[range(9000, 9600)]), []);
+    }
+
+    #[test]
+    fn reserve_and_release_write_the_kernel_format() {
+        let (root, proc) = fixture("8080,9000-9010\n");
+        let path = root.path().join(RESERVED_PORTS);
+
+        let reservations = reserve_ports(&proc, &[range(8081, 8081), range(9011, 9012)]).unwrap();
+        assert_eq!(reservations.reserved, [range(8080, 8081), range(9000, 9012)]);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "8080-8081,9000-9012\n");
+
+        release_ports(&proc, &[range(9005, 9005)]).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "8080-8081,9000-9004,9006-9012\n");
+    }
+
+    #[test]
+    fn releasing_everything_clears_to_a_newline() {
+        let (root, proc) = fixture("8080\n");
+
+        let reservations = release_ports(&proc, &[range(8080, 8080)]).unwrap();
+        assert!(reservations.reserved.is_empty());
+        assert_eq!(fs::read_to_string(root.path().join(RESERVED_PORTS)).unwrap(), "\n");
+    }
+
+    #[test]
+    fn rejects_port_zero_and_reversed_ranges() {
+        let (root, proc) = fixture("8080\n");
+
+        for ports in [vec![], vec![range(0, 10)], vec![range(9010, 9000)]] {
+            let error = reserve_ports(&proc, &ports).unwrap_err();
+            assert!(
+                matches!(error.downcast_ref(), Some(PorterError::InvalidInput { .. })),
+                "{:?} gave {}",
+                ports,
+                error
+            );
+            assert!(release_ports(&proc, &ports).is_err());
+        }
+        assert_eq!(fs::read_to_string(root.path().join(RESERVED_PORTS)).unwrap(), "8080\n");
+    }
+}
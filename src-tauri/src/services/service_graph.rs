@@ -0,0 +1,304 @@
+use crate::models::{InterfaceAddress, ServiceEdge, ServiceGraph, ServiceNode};
+use crate::platform::NetworkConnection;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::net::IpAddr;
+
+struct Listener {
+    ip: IpAddr,
+    port: u16,
+    pid: u32,
+}
+
+/// Link each established client socket whose remote end is a local listener
+/// to that listener's process
+pub fn build(
+    connections: &[NetworkConnection],
+    interfaces: &[InterfaceAddress],
+    process_name: impl Fn(u32) -> Option<String>,
+) -> ServiceGraph {
+    let local_ips: HashSet<IpAddr> = interfaces
+        .iter()
+        .filter_map(|iface| parse_ip(&iface.address))
+        .collect();
+
+    let listeners: Vec<Listener> = connections
+        .iter()
+        .filter(|conn| conn.is_listening())
+        .filter_map(|conn| {
+            Some(Listener {
+                ip: parse_ip(&conn.local_address)?,
+                port: conn.local_port,
+                pid: conn.pid,
+            })
+        })
+        .collect();
+
+    let mut edges: BTreeMap<(String, String, u16), usize> = BTreeMap::new();
+    for conn in connections.iter().filter(|conn| conn.state == "ESTABLISHED" && conn.pid > 0) {
+        let Some(remote) = parse_ip(&conn.remote_address) else {
+            continue;
+        };
+        if !remote.is_loopback() && !local_ips.contains(&remote) {
+            continue;
+        }
+
+        // A listener on the exact address wins over a wildcard one
+        let on_port = || listeners.iter().filter(|listener| listener.port == conn.remote_port);
+        let Some(listener) = on_port()
+            .find(|listener| listener.ip == remote)
+            .or_else(|| on_port().find(|listener| listener.ip.is_unspecified()))
+        else {
+            continue;
+        };
+
+        if listener.pid == conn.pid {
+            continue;
+        }
+
+        let key = (node_id(conn.pid, conn.local_port), node_id(listener.pid, listener.port), listener.port);
+        *edges.entry(key).or_default() += 1;
+    }
+
+    let mut node_ids: BTreeSet<&str> = BTreeSet::new();
+    for (from, to, _) in edges.keys() {
+        node_ids.insert(from);
+        node_ids.insert(to);
+    }
+
+    let nodes = node_ids
+        .into_iter()
+        .map(|id| {
+            let pid = id.strip_prefix("pid-").and_then(|pid| pid.parse().ok());
+            let listening_ports: BTreeSet<u16> = match pid {
+                Some(pid) => listeners
+                    .iter()
+                    .filter(|listener| listener.pid == pid)
+                    .map(|listener| listener.port)
+                    .collect(),
+                None => id.strip_prefix("port-").and_then(|port| port.parse().ok()).into_iter().collect(),
+            };
+            let name = match pid {
+                Some(pid) => process_name(pid).unwrap_or_else(|| "Unknown".to_string()),
+                None => format!("port {}", id.trim_start_matches("port-")),
+            };
+
+            ServiceNode {
+                id: id.to_string(),
+                pid,
+                name,
+                listening_ports: listening_ports.into_iter().collect(),
+            }
+        })
+        .collect();
+
+    ServiceGraph {
+        generated_at: chrono::Utc::now(),
+        nodes,
+        edges: edges
+            .into_iter()
+            .map(|((from, to, port), connections)| ServiceEdge {
+                from,
+                to,
+                port,
+                connections,
+            })
+            .collect(),
+    }
+}
+
+pub fn render_dot(graph: &ServiceGraph) -> String {
+    let mut out = String::from("digraph services {\n  rankdir=LR;\n  node [shape=box];\n");
+
+    for node in &graph.nodes {
+        out.push_str(&format!(
+            "  \"{}\" [label=\"{}\"];\n",
+            node.id,
+            node_label(node, "\\n").replace('"', "\\\"")
+        ));
+    }
+    for edge in &graph.edges {
+        out.push_str(&format!(
+            "  \"{}\" -> \"{}\" [label=\"{}\"];\n",
+            edge.from,
+            edge.to,
+            edge_label(edge)
+        ));
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+pub fn render_mermaid(graph: &ServiceGraph) -> String {
+    // Mermaid ids can't contain dashes
+    let id = |id: &str| id.replace('-', "_");
+    let mut out = String::from("graph LR\n");
+
+    for node in &graph.nodes {
+        out.push_str(&format!(
+            "  {}[\"{}\"]\n",
+            id(&node.id),
+            node_label(node, "<br/>").replace('"', "#quot;")
+        ));
+    }
+    for edge in &graph.edges {
+        out.push_str(&format!(
+            "  {} -->|\"{}\"| {}\n",
+            id(&edge.from),
+            edge_label(edge),
+            id(&edge.to)
+        ));
+    }
+
+    out
+}
+
+/// `node (PID 12)` followed by its listening ports on the next line
+fn node_label(node: &ServiceNode, line_break: &str) -> String {
+    let mut label = match node.pid {
+        Some(pid) => format!("{} (PID {})", node.name, pid),
+        None => node.name.clone(),
+    };
+
+    if !node.listening_ports.is_empty() {
+        let ports: Vec<String> = node.listening_ports.iter().map(|port| format!(":{}", port)).collect();
+        label.push_str(line_break);
+        label.push_str(&ports.join(" "));
+    }
+
+    label
+}
+
+fn edge_label(edge: &ServiceEdge) -> String {
+    if edge.connections == 1 {
+        format!(":{}", edge.port)
+    } else {
+        format!(":{} ×{}", edge.port, edge.connections)
+    }
+}
+
+/// Listeners whose owner is hidden still get a node, one per port
+fn node_id(pid: u32, port: u16) -> String {
+    if pid > 0 {
+        format!("pid-{}", pid)
+    } else {
+        format!("port-{}", port)
+    }
+}
+
+/// A v4-mapped address like ::ffff:127.0.0.1 is treated as its IPv4 address
+fn parse_ip(address: &str) -> Option<IpAddr> {
+    let ip = address.parse::<IpAddr>().ok()?;
+    Some(match ip {
+        IpAddr::V6(v6) => v6.to_ipv4_mapped().map(IpAddr::V4).unwrap_or(ip),
+        v4 => v4,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Protocol;
+
+    fn conn(local: (&str, u16), remote: (&str, u16), state: &str, pid: u32) -> NetworkConnection {
+        NetworkConnection {
+            local_address: local.0.to_string(),
+            local_port: local.1,
+            remote_address: remote.0.to_string(),
+            remote_port: remote.1,
+            protocol: Protocol::TCP,
+            pid,
+            shared_with: Vec::new(),
+            state: state.to_string(),
+            unattributed: None,
+            namespace: None,
+            tcp: None,
+        }
+    }
+
+    /// An API (200) using Postgres (100) over two pooled connections and a Redis
+    /// whose owner is hidden, with a worker (300) calling the API on the LAN address
+    fn sample_graph() -> ServiceGraph {
+        let connections = [
+            conn(("127.0.0.1", 5432), ("0.0.0.0", 0), "LISTEN", 100),
+            conn(("0.0.0.0", 6379), ("0.0.0.0", 0), "LISTEN", 0),
+            conn(("::", 3000), ("::", 0), "LISTEN", 200),
+            conn(("127.0.0.1", 41000), ("127.0.0.1", 5432), "ESTABLISHED", 200),
+            conn(("127.0.0.1", 41002), ("127.0.0.1", 5432), "ESTABLISHED", 200),
+            conn(("127.0.0.1", 41010), ("127.0.0.1", 6379), "ESTABLISHED", 200),
+            conn(("::ffff:192.168.1.10", 52000), ("::ffff:192.168.1.10", 3000), "ESTABLISHED", 300),
+            // Not local services: a remote peer, a closing socket and a loop back to itself
+            conn(("192.168.1.10", 53000), ("93.184.216.34", 443), "ESTABLISHED", 200),
+            conn(("127.0.0.1", 41020), ("127.0.0.1", 5432), "TIME_WAIT", 0),
+            conn(("127.0.0.1", 41030), ("127.0.0.1", 3000), "ESTABLISHED", 200),
+        ];
+        let interfaces = [InterfaceAddress {
+            interface: "eth0".to_string(),
+            address: "192.168.1.10".to_string(),
+            prefix_len: 24,
+            loopback: false,
+            up: true,
+        }];
+        let names = |pid: u32| match pid {
+            100 => Some("postgres".to_string()),
+            200 => Some("node \"api\"".to_string()),
+            _ => None,
+        };
+
+        build(&connections, &interfaces, names)
+    }
+
+    #[test]
+    fn links_local_clients_to_listeners() {
+        let graph = sample_graph();
+
+        let edges: Vec<_> = graph
+            .edges
+            .iter()
+            .map(|e| (e.from.as_str(), e.to.as_str(), e.port, e.connections))
+            .collect();
+        assert_eq!(
+            edges,
+            [
+                ("pid-200", "pid-100", 5432, 2),
+                ("pid-200", "port-6379", 6379, 1),
+                ("pid-300", "pid-200", 3000, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn dot_export() {
+        assert_eq!(
+            render_dot(&sample_graph()),
+            r#"digraph services {
+  rankdir=LR;
+  node [shape=box];
+  "pid-100" [label="postgres (PID 100)\n:5432"];
+  "pid-200" [label="node \"api\" (PID 200)\n:3000"];
+  "pid-300" [label="Unknown (PID 300)"];
+  "port-6379" [label="port 6379\n:6379"];
+  "pid-200" -> "pid-100" [label=":5432 ×2"];
+  "pid-200" -> "port-6379" [label=":6379"];
+  "pid-300" -> "pid-200" [label=":3000"];
+}
+"#
+        );
+    }
+
+    #[test]
+    fn mermaid_export() {
+        assert_eq!(
+            render_mermaid(&sample_graph()),
+            r#"graph LR
+  pid_100["postgres (PID 100)<br/>:5432"]
+  pid_200["node #quot;api#quot; (PID 200)<br/>:3000"]
+  pid_300["Unknown (PID 300)"]
+  port_6379["port 6379<br/>:6379"]
+  pid_200 -->|":5432 ×2"| pid_100
+  pid_200 -->|":6379"| port_6379
+  pid_300 -->|":3000"| pid_200
+"#
+        );
+    }
+}
@@ -0,0 +1,18 @@
+use crate::models::SystemInfo;
+use super::{ProcFs, interfaces};
+use sysinfo::System;
+
+pub fn collect(proc: &ProcFs) -> SystemInfo {
+    let sys = System::new_all();
+
+    SystemInfo {
+        os: std::env::consts::OS.to_string(),
+        os_version: System::long_os_version().unwrap_or_else(|| "Unknown".to_string()),
+        hostname: System::host_name().unwrap_or_else(|| "Unknown".to_string()),
+        cpu_count: sys.cpus().len(),
+        total_memory: sys.total_memory(),
+        uptime_secs: proc.uptime_secs().unwrap_or_else(System::uptime),
+        interfaces: interfaces::network_interfaces(),
+        network: proc.network_limits(),
+    }
+}
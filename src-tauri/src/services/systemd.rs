@@ -0,0 +1,149 @@
+use crate::models::{SystemdUnit, UnitScope};
+use anyhow::Result;
+
+#[cfg(target_os = "linux")]
+use crate::error::PorterError;
+
+#[derive(Debug, Clone, Copy)]
+pub enum UnitAction {
+    Stop,
+    Restart,
+}
+
+impl UnitAction {
+    pub fn verb(self) -> &'static str {
+        match self {
+            UnitAction::Stop => "stop",
+            UnitAction::Restart => "restart",
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn method(self) -> &'static str {
+        match self {
+            UnitAction::Stop => "StopUnit",
+            UnitAction::Restart => "RestartUnit",
+        }
+    }
+}
+
+/// The systemd unit a process belongs to (Linux only)
+pub fn unit_for_pid(pid: u32) -> Option<SystemdUnit> {
+    #[cfg(target_os = "linux")]
+    {
+        crate::platform::cgroup_path(pid).and_then(|cgroup| unit_from_cgroup(&cgroup))
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = pid;
+        None
+    }
+}
+
+/// Work out the unit from a cgroup v2 path such as `/system.slice/nginx.service` or
+/// `/user.slice/user-1000.slice/user@1000.service/app.slice/vite.service`
+pub fn unit_from_cgroup(cgroup: &str) -> Option<SystemdUnit> {
+    let components: Vec<&str> = cgroup.split('/').filter(|c| !c.is_empty()).collect();
+
+    let user_manager = components
+        .iter()
+        .position(|c| c.starts_with("user@") && c.ends_with(".service"));
+
+    // Units may delegate sub-cgroups, so take the deepest component that names a unit
+    let find_unit = |from: usize| {
+        components[from..]
+            .iter()
+            .rev()
+            .find(|c| c.ends_with(".service") || c.ends_with(".scope"))
+            .map(|c| c.to_string())
+    };
+
+    let (name, scope, uid) = match user_manager {
+        Some(index) => match find_unit(index + 1) {
+            Some(name) => {
+                let uid = components[index]
+                    .trim_start_matches("user@")
+                    .trim_end_matches(".service")
+                    .parse()
+                    .ok();
+                (name, UnitScope::User, uid)
+            }
+            // The process is the user manager itself
+            None => (components[index].to_string(), UnitScope::System, None),
+        },
+        None => (find_unit(0)?, UnitScope::System, None),
+    };
+
+    Some(SystemdUnit {
+        managed: name.ends_with(".service"),
+        name,
+        scope,
+        uid,
+    })
+}
+
+/// Units Porter never stops or restarts: a user's whole service manager,
+/// PID 1's own scope, and slices that group many units
+pub fn is_protected_unit(name: &str) -> bool {
+    (name.starts_with("user@") && name.ends_with(".service")) || name == "init.scope" || name.ends_with(".slice")
+}
+
+/// Stop or restart a unit through the systemd D-Bus API. System units may prompt
+/// for authentication through polkit.
+pub fn manage_unit(unit: &SystemdUnit, action: UnitAction) -> Result<()> {
+    #[cfg(target_os = "linux")]
+    {
+        use zbus::blocking::{Connection, Proxy};
+        use zbus::proxy::MethodFlags;
+        use zbus::zvariant::OwnedObjectPath;
+
+        let connection = match unit.scope {
+            UnitScope::System => Connection::system(),
+            UnitScope::User => {
+                // Only our own user manager is reachable over the session bus
+                if unit.uid.is_some_and(|uid| uid != unsafe { libc::getuid() }) {
+                    return Err(PorterError::Unsupported {
+                        operation: format!("Managing another user's unit {}", unit.name),
+                        os: std::env::consts::OS.to_string(),
+                    }
+                    .into());
+                }
+                Connection::session()
+            }
+        }
+        .map_err(|e| PorterError::backend_unavailable("systemd", e))?;
+
+        let manager = Proxy::new(
+            &connection,
+            "org.freedesktop.systemd1",
+            "/org/freedesktop/systemd1",
+            "org.freedesktop.systemd1.Manager",
+        )
+        .map_err(|e| PorterError::backend_unavailable("systemd", e))?;
+
+        manager
+            .call_with_flags::<_, _, OwnedObjectPath>(
+                action.method(),
+                MethodFlags::AllowInteractiveAuth.into(),
+                &(unit.name.as_str(), "replace"),
+            )
+            .map_err(|e| match &e {
+                zbus::Error::MethodError(name, _, _)
+                    if name.as_str() == "org.freedesktop.DBus.Error.AccessDenied"
+                        || name.as_str() == "org.freedesktop.DBus.Error.InteractiveAuthorizationRequired" =>
+                {
+                    anyhow::Error::from(PorterError::AuthenticationCancelled)
+                }
+                _ => anyhow::anyhow!("systemd failed to {} {}: {}", action.verb(), unit.name, e),
+            })?;
+
+        Ok(())
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = (unit, action);
+        Err(crate::error::PorterError::unsupported("Managing systemd units").into())
+    }
+}
@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+use sysinfo::{Gid, Uid};
+
+/// Resolves numeric user and group IDs to names, caching every lookup.
+/// On Unix this goes through NSS (`getpwuid_r` / `getgrgid_r`), so LDAP and
+/// other directory users resolve the same way `ls -l` would show them.
+pub struct UserResolver {
+    users: HashMap<String, Option<String>>,
+    groups: HashMap<u32, Option<String>>,
+}
+
+impl UserResolver {
+    pub fn new() -> Self {
+        Self {
+            users: HashMap::new(),
+            groups: HashMap::new(),
+        }
+    }
+
+    pub fn user_name(&mut self, uid: &Uid) -> Option<String> {
+        self.users
+            .entry(uid.to_string())
+            .or_insert_with(|| lookup_uid(uid))
+            .clone()
+    }
+
+    pub fn group_name(&mut self, gid: Gid) -> Option<String> {
+        let gid = *gid;
+        self.groups.entry(gid).or_insert_with(|| lookup_group(gid)).clone()
+    }
+}
+
+/// Numeric UID, which only exists on Unix (Windows uses SIDs)
+pub fn numeric_uid(uid: &Uid) -> Option<u32> {
+    #[cfg(not(target_os = "windows"))]
+    {
+        Some(**uid)
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let _ = uid;
+        None
+    }
+}
+
+/// Name of the user running Porter
+pub fn current_user() -> String {
+    #[cfg(not(target_os = "windows"))]
+    if let Some(name) = lookup_user(unsafe { libc::getuid() }) {
+        return name;
+    }
+
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_else(|_| "unknown".to_string())
+}
+
+#[cfg(not(target_os = "windows"))]
+fn lookup_uid(uid: &Uid) -> Option<String> {
+    lookup_user(**uid)
+}
+
+#[cfg(not(target_os = "windows"))]
+fn lookup_user(uid: u32) -> Option<String> {
+    let mut buffer = vec![0 as libc::c_char; 1024];
+
+    loop {
+        let mut entry: libc::passwd = unsafe { std::mem::zeroed() };
+        let mut result = std::ptr::null_mut();
+        let code = unsafe {
+            libc::getpwuid_r(uid, &mut entry, buffer.as_mut_ptr(), buffer.len(), &mut result)
+        };
+
+        // Entries with many fields may not fit; retry with a larger buffer
+        if code == libc::ERANGE && buffer.len() < 1 << 20 {
+            buffer.resize(buffer.len() * 2, 0);
+            continue;
+        }
+        if code != 0 || result.is_null() {
+            return None;
+        }
+
+        let name = unsafe { std::ffi::CStr::from_ptr(entry.pw_name) };
+        return Some(name.to_string_lossy().into_owned());
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn lookup_group(gid: u32) -> Option<String> {
+    let mut buffer = vec![0 as libc::c_char; 1024];
+
+    loop {
+        let mut entry: libc::group = unsafe { std::mem::zeroed() };
+        let mut result = std::ptr::null_mut();
+        let code = unsafe {
+            libc::getgrgid_r(gid, &mut entry, buffer.as_mut_ptr(), buffer.len(), &mut result)
+        };
+
+        // Groups with many members may not fit; retry with a larger buffer
+        if code == libc::ERANGE && buffer.len() < 1 << 20 {
+            buffer.resize(buffer.len() * 2, 0);
+            continue;
+        }
+        if code != 0 || result.is_null() {
+            return None;
+        }
+
+        let name = unsafe { std::ffi::CStr::from_ptr(entry.gr_name) };
+        return Some(name.to_string_lossy().into_owned());
+    }
+}
+
+// Windows identifies users by SID rather than a numeric UID, so names come from sysinfo instead
+#[cfg(target_os = "windows")]
+fn lookup_uid(uid: &Uid) -> Option<String> {
+    sysinfo::Users::new_with_refreshed_list()
+        .get_user_by_id(uid)
+        .map(|user| user.name().to_string())
+}
+
+#[cfg(target_os = "windows")]
+fn lookup_group(_gid: u32) -> Option<String> {
+    None
+}
@@ -1,6 +1,7 @@
-import { useState, useMemo, useEffect } from 'react';
+import { useState, useMemo, useEffect, useCallback } from 'react';
 import { QueryClient, QueryClientProvider } from '@tanstack/react-query';
 import SimpleBar from 'simplebar-react';
+import { Network } from 'lucide-react';
 import 'simplebar-react/dist/simplebar.min.css';
 import { Header } from './components/dashboard/Header';
 import { SearchBar } from './components/dashboard/SearchBar';
@@ -9,8 +10,17 @@ import { StatsCard } from './components/dashboard/StatsCard';
 import { PortListItem } from './components/dashboard/PortListItem';
 import { StatusFilter } from './components/dashboard/StatusFilter';
 import { PortScanLoader } from './components/dashboard/PortScanLoader';
-import { useAllPorts, useRefreshPorts } from './hooks/usePorts';
-import { killProcess, isElevated } from './lib/tauri';
+import { InterfaceGroups } from './components/dashboard/InterfaceGroups';
+import { useAllPorts, useRefreshPorts, useScannedPorts } from './hooks/usePorts';
+import {
+  killProcess,
+  killProcessPrivileged,
+  hasPrivilegedHelper,
+  setServicePorts,
+  isElevated,
+  isCommandError,
+  errorMessage,
+} from './lib/tauri';
 import { Button } from './components/ui/button';
 import { Port } from './types/api';
 import { Toaster } from './components/ui/toaster';
@@ -22,8 +32,9 @@ function AppContent() {
   const [searchQuery, setSearchQuery] = useState('');
   const [isAdmin, setIsAdmin] = useState(false);
   const [showAllPorts, setShowAllPorts] = useState(false);
+  const [groupByInterface, setGroupByInterface] = useState(false);
   const [selectedStatuses, setSelectedStatuses] = useState<Set<string>>(
-    new Set(['free', 'occupied', 'system'])
+    new Set(['free', 'occupied', 'system', 'unknown'])
   );
   const { toast } = useToast();
 
@@ -61,27 +72,30 @@ function AppContent() {
     };
   }, []);
 
+  // Pinned ports are the ones the user expects services on, on top of the
+  // common ones; warn when outgoing connections grab them
+  useEffect(() => {
+    setServicePorts([...pinnedPortNumbers]).catch(error => {
+      console.error('Failed to update service ports:', error);
+    });
+  }, [pinnedPortNumbers]);
+
+  // Pinned ports that aren't running still need their ephemeral range checks
+  const pinnedPortArray = useMemo(() => [...pinnedPortNumbers].sort((a, b) => a - b), [pinnedPortNumbers]);
+  const { data: scannedPinnedPorts = [] } = useScannedPorts(pinnedPortArray);
+
   // Separate pinned and other ports from all ports
-  // Create Port objects for all pinned ports, even if not currently running
   const { pinnedPortsList, otherPortsList } = useMemo(() => {
     const pinned: Port[] = [];
     const other: Port[] = [];
     const allPortsMap = new Map(allPorts.map(p => [p.port, p]));
+    const scannedMap = new Map(scannedPinnedPorts.map(p => [p.port, p]));
 
-    // Add all pinned ports (create placeholder for non-running ones)
+    // Add all pinned ports, using the scan for ones that aren't running
     pinnedPortNumbers.forEach(portNum => {
-      const existingPort = allPortsMap.get(portNum);
+      const existingPort = allPortsMap.get(portNum) ?? scannedMap.get(portNum);
       if (existingPort) {
         pinned.push(existingPort);
-      } else {
-        // Create a placeholder port object for non-running pinned ports
-        pinned.push({
-          port: portNum,
-          status: 'free',
-          protocol: 'TCP',
-          ip_address: '0.0.0.0',
-          process: undefined
-        });
       }
     });
 
@@ -96,7 +110,7 @@ function AppContent() {
     });
 
     return { pinnedPortsList: pinned, otherPortsList: other };
-  }, [allPorts, pinnedPortNumbers]);
+  }, [allPorts, scannedPinnedPorts, pinnedPortNumbers]);
 
   const isLoading = isLoadingAll;
   const isRefetching = isRefetchingAll;
@@ -127,42 +141,31 @@ function AppContent() {
     checkElevation();
   }, []);
 
-  // Filter pinned and other ports separately
-  const { filteredPinnedPorts, filteredOtherPorts } = useMemo(() => {
-    const applyFilters = (ports: Port[]) => {
-      let filtered = ports;
-
-      // Filter by status
-      filtered = filtered.filter((port) => selectedStatuses.has(port.status));
-
-      // Filter by search query
-      if (searchQuery) {
-        filtered = filtered.filter((port) => {
-          return port.port.toString().includes(searchQuery);
-        });
-      }
-
-      return filtered;
-    };
-
-    return {
-      filteredPinnedPorts: applyFilters(pinnedPortsList),
-      filteredOtherPorts: applyFilters(otherPortsList)
-    };
-  }, [pinnedPortsList, otherPortsList, searchQuery, selectedStatuses]);
+  // Filter by status and search query
+  const matchesFilters = useCallback(
+    (port: Port) =>
+      selectedStatuses.has(port.status) && (!searchQuery || port.port.toString().includes(searchQuery)),
+    [searchQuery, selectedStatuses]
+  );
 
-  const handleKillProcess = async (pid: number) => {
-    if (!isAdmin) {
-      toast({
-        variant: "destructive",
-        title: "Administrator privileges required",
-        description: "Please restart Porter as Administrator to kill processes.",
-      });
-      return;
-    }
+  // Filter pinned and other ports separately
+  const { filteredPinnedPorts, filteredOtherPorts } = useMemo(() => ({
+    filteredPinnedPorts: pinnedPortsList.filter(matchesFilters),
+    filteredOtherPorts: otherPortsList.filter(matchesFilters)
+  }), [pinnedPortsList, otherPortsList, matchesFilters]);
 
+  const handleKillProcess = async (pid: number, port: number) => {
     try {
-      await killProcess(pid);
+      try {
+        await killProcess(pid);
+      } catch (error) {
+        // Another user's process: go through the pkexec helper, which asks for authentication
+        const needsPrivileges = isCommandError(error) && error.code === 'permission_denied';
+        if (!needsPrivileges || isAdmin || !(await hasPrivilegedHelper())) {
+          throw error;
+        }
+        await killProcessPrivileged(pid, port);
+      }
       refreshPorts();
       toast({
         title: "Process terminated",
@@ -170,11 +173,11 @@ function AppContent() {
       });
     } catch (error) {
       console.error('Failed to kill process:', error);
-      const errorMessage = error instanceof Error ? error.message : String(error);
+      const needsPrivileges = isCommandError(error) && error.code === 'permission_denied';
       toast({
         variant: "destructive",
-        title: "Failed to kill process",
-        description: errorMessage,
+        title: needsPrivileges ? "Administrator privileges required" : "Failed to kill process",
+        description: errorMessage(error),
       });
     }
   };
@@ -218,12 +221,21 @@ function AppContent() {
                   onStatusToggle={handleStatusToggle}
                 />
               </div>
+              <Button
+                variant={groupByInterface ? 'default' : 'outline'}
+                size="sm"
+                onClick={() => setGroupByInterface(!groupByInterface)}
+                className="gap-2 text-nowrap"
+              >
+                <Network className="w-4 h-4" />
+                By Interface
+              </Button>
             </div>
 
             {/* Port List Header */}
             <div className="flex gap-2 items-center">
               <h2 className="text-sm font-semibold text-foreground">
-                {showAllPorts ? '🌐 All Ports' : '📌 Pinned Ports'}
+                {groupByInterface ? '🔌 Listeners by Interface' : showAllPorts ? '🌐 All Ports' : '📌 Pinned Ports'}
                 {searchQuery && ` (${filteredPinnedPorts.length + filteredOtherPorts.length} results)`}
               </h2>
             </div>
@@ -232,7 +244,15 @@ function AppContent() {
           {/* Scrollable Port List */}
           <div className="overflow-hidden flex-1">
             <SimpleBar style={{ height: '100%' }}>
-              {isLoading ? (
+              {groupByInterface ? (
+                <div className="pr-2 pb-8">
+                  <InterfaceGroups
+                    matches={matchesFilters}
+                    pinnedPortNumbers={pinnedPortNumbers}
+                    onKill={handleKillProcess}
+                  />
+                </div>
+              ) : isLoading ? (
                 <PortScanLoader />
               ) : (
                 <div className="pr-2 pb-8 space-y-2">
@@ -0,0 +1,80 @@
+import { Network } from 'lucide-react';
+import { Badge } from '@/components/ui/badge';
+import { usePortsByInterface } from '@/hooks/usePorts';
+import { InterfaceKind, Port } from '@/types/api';
+import { PortListItem } from './PortListItem';
+import { PortScanLoader } from './PortScanLoader';
+
+interface InterfaceGroupsProps {
+  matches: (port: Port) => boolean;
+  pinnedPortNumbers: Set<number>;
+  onKill: (pid: number, port: number) => void;
+}
+
+const kindLabels: Record<InterfaceKind, string> = {
+  loopback: 'loopback',
+  physical: 'ethernet',
+  wireless: 'wi-fi',
+  container_bridge: 'container bridge',
+  bridge: 'bridge',
+  virtual: 'virtual',
+};
+
+// Listeners grouped by the interface they can be reached on; wildcard binds
+// show up under every interface they cover
+export function InterfaceGroups({ matches, pinnedPortNumbers, onKill }: InterfaceGroupsProps) {
+  const { data: groups = [], isLoading } = usePortsByInterface();
+
+  if (isLoading) {
+    return <PortScanLoader />;
+  }
+
+  const visible = groups
+    .map(group => ({ ...group, ports: group.ports.filter(matches) }))
+    .filter(group => group.ports.length > 0);
+
+  if (visible.length === 0) {
+    return <div className="py-8 text-sm text-center text-muted-foreground">No listeners match</div>;
+  }
+
+  return (
+    <div className="space-y-4">
+      {visible.map(group => {
+        const iface = group.interface;
+        return (
+          <section key={iface?.name ?? 'unassigned'} className="space-y-2">
+            <div className="flex gap-2 items-center text-sm">
+              <Network className="w-4 h-4 text-muted-foreground" />
+              <span className="font-semibold text-foreground">{iface?.name ?? 'No interface'}</span>
+              {iface ? (
+                <>
+                  <Badge variant="outline" className="text-[10px] px-1.5 py-0 h-5">
+                    {kindLabels[iface.kind]}
+                  </Badge>
+                  {!iface.up && (
+                    <Badge variant="secondary" className="text-[10px] px-1.5 py-0 h-5">
+                      down
+                    </Badge>
+                  )}
+                  <span className="text-xs truncate text-muted-foreground">
+                    {iface.addresses.map(addr => `${addr.address}/${addr.prefix_len}`).join(', ')}
+                  </span>
+                </>
+              ) : (
+                <span className="text-xs text-muted-foreground">bound to addresses no interface holds</span>
+              )}
+            </div>
+            {group.ports.map(port => (
+              <PortListItem
+                key={`${port.protocol}-${port.ip_address}-${port.port}`}
+                port={port}
+                onKill={onKill}
+                isPinned={pinnedPortNumbers.has(port.port)}
+              />
+            ))}
+          </section>
+        );
+      })}
+    </div>
+  );
+}
@@ -0,0 +1,43 @@
+import { useQuery } from '@tanstack/react-query';
+import { getKillImpact } from '@/lib/tauri';
+
+interface KillImpactSummaryProps {
+  pid: number;
+}
+
+export function KillImpactSummary({ pid }: KillImpactSummaryProps) {
+  const { data: impact, isLoading } = useQuery({
+    queryKey: ['kill-impact', pid],
+    queryFn: () => getKillImpact(pid),
+    staleTime: 0,
+  });
+
+  if (isLoading) {
+    return <div className="text-xs text-muted-foreground">Checking what depends on it…</div>;
+  }
+
+  if (!impact) return null;
+
+  const localClients = impact.clients.filter(client => client.local);
+
+  return (
+    <div className="space-y-1 text-xs">
+      <div className="text-foreground">{impact.summary}</div>
+      {localClients.length > 0 && (
+        <div className="text-muted-foreground">
+          Local clients:{' '}
+          {localClients
+            .map(client =>
+              client.process_name ? `${client.process_name} (${client.pid})` : `${client.remote_address}:${client.remote_port}`
+            )
+            .join(', ')}
+        </div>
+      )}
+      {impact.children.length > 0 && (
+        <div className="text-muted-foreground">
+          Children: {impact.children.map(child => `${child.name} (${child.pid})`).join(', ')}
+        </div>
+      )}
+    </div>
+  );
+}
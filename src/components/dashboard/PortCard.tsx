@@ -16,6 +16,8 @@ export function PortCard({ port, onClick }: PortCardProps) {
         return 'text-destructive';
       case 'system':
         return 'text-warning';
+      case 'unknown':
+        return 'text-muted-foreground';
     }
   };
 
@@ -27,6 +29,8 @@ export function PortCard({ port, onClick }: PortCardProps) {
         return 'bg-destructive/10 hover:bg-destructive/20';
       case 'system':
         return 'bg-warning/10 hover:bg-warning/20';
+      case 'unknown':
+        return 'bg-muted hover:bg-muted/80';
     }
   };
 
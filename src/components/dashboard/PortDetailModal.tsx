@@ -1,16 +1,25 @@
+import { useQuery } from '@tanstack/react-query';
 import { Port } from '@/types/api';
 import { X, AlertTriangle } from 'lucide-react';
 import { Button } from '@/components/ui/button';
 import { Card, CardContent, CardHeader, CardTitle } from '@/components/ui/card';
 import { formatBytes, formatUptime } from '@/lib/utils';
+import { getConnectionDetails } from '@/lib/tauri';
 
 interface PortDetailModalProps {
   port: Port | null;
   onClose: () => void;
-  onKill?: (pid: number) => void;
+  onKill?: (pid: number, port: number) => void;
 }
 
 export function PortDetailModal({ port, onClose, onKill }: PortDetailModalProps) {
+  const { data: connections = [] } = useQuery({
+    queryKey: ['connections', port?.port],
+    queryFn: () => getConnectionDetails(port!.port),
+    enabled: !!port,
+    refetchInterval: 2000,
+  });
+
   if (!port) return null;
 
   return (
@@ -34,9 +43,58 @@ export function PortDetailModal({ port, onClose, onKill }: PortDetailModalProps)
               <div>{port.protocol}</div>
               <div className="text-muted-foreground">IP Address:</div>
               <div className="font-mono">{port.ip_address}:{port.port}</div>
+              {port.container && (
+                <>
+                  <div className="text-muted-foreground">Container:</div>
+                  <div className="font-mono text-xs break-all">
+                    {port.container.name} ({port.container.image}) → {port.container.container_port}
+                    {port.container.compose_project && ` · ${port.container.compose_project}`}
+                  </div>
+                </>
+              )}
             </div>
           </div>
 
+          {/* Sockets */}
+          {connections.length > 0 && (
+            <div className="border-t pt-4">
+              <h3 className="font-semibold mb-2">Connections</h3>
+              <div className="space-y-1 text-xs font-mono">
+                {connections.map((conn) => {
+                  const tcp = conn.tcp;
+                  return (
+                    <div
+                      key={`${conn.protocol}-${tcp?.inode ?? conn.pid}-${conn.local_address}-${conn.remote_address}:${conn.remote_port}`}
+                      className="grid grid-cols-4 gap-2"
+                    >
+                      <div className="truncate">
+                        {conn.state === 'LISTEN' || !conn.remote_port ? conn.state || conn.protocol : `${conn.remote_address}:${conn.remote_port}`}
+                      </div>
+                      {tcp ? (
+                        <>
+                          <div title={conn.state === 'LISTEN' ? 'Accept queue / backlog limit' : 'Receive / send queue'}>
+                            {conn.state === 'LISTEN'
+                              ? `queued ${tcp.rx_queue}${tcp.max_backlog !== undefined ? ` / ${tcp.max_backlog}` : ''}`
+                              : `rx ${tcp.rx_queue} · tx ${tcp.tx_queue}`}
+                          </div>
+                          <div>
+                            {tcp.timer !== 'off' && `${tcp.timer} `}
+                            {tcp.retransmits > 0 && `retrans ${tcp.retransmits}`}
+                          </div>
+                          <div className="text-muted-foreground">
+                            {tcp.info && conn.state !== 'LISTEN' && `rtt ${(tcp.info.rtt_us / 1000).toFixed(1)}ms · cwnd ${tcp.info.snd_cwnd}`}
+                          </div>
+                        </>
+                      ) : (
+                        <div className="col-span-3 text-muted-foreground">{conn.protocol} · PID {conn.pid}</div>
+                      )}
+                    </div>
+                  );
+                })}
+              </div>
+            </div>
+          )}
+
           {/* Process Info */}
           {port.process && (
             <>
@@ -57,6 +115,26 @@ export function PortDetailModal({ port, onClose, onKill }: PortDetailModalProps)
                       <div className="font-mono text-xs break-all">{port.process.working_dir}</div>
                     </>
                   )}
+                  {port.process.user && (
+                    <>
+                      <div className="text-muted-foreground">User:</div>
+                      <div>
+                        {port.process.user}
+                        {port.process.uid !== undefined && (
+                          <span className="text-muted-foreground font-mono text-xs"> ({port.process.uid})</span>
+                        )}
+                      </div>
+                    </>
+                  )}
+                  {port.process.systemd_unit && (
+                    <>
+                      <div className="text-muted-foreground">Systemd Unit:</div>
+                      <div className="font-mono text-xs break-all">
+                        {port.process.systemd_unit.name}
+                        {port.process.systemd_unit.scope === 'user' && ' (user)'}
+                      </div>
+                    </>
+                  )}
                   <div className="text-muted-foreground">Started:</div>
                   <div>{formatUptime(port.process.started_at)} ago</div>
                 </div>
@@ -82,7 +160,7 @@ export function PortDetailModal({ port, onClose, onKill }: PortDetailModalProps)
                   <Button
                     variant="destructive"
                     onClick={() => {
-                      onKill(port.process!.pid);
+                      onKill(port.process!.pid, port.port);
                       onClose();
                     }}
                   >
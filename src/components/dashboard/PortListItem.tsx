@@ -1,8 +1,9 @@
-import { Trash2, Pin } from 'lucide-react';
+import { Trash2, Pin, AlertTriangle } from 'lucide-react';
 import { Button } from '@/components/ui/button';
 import { Badge } from '@/components/ui/badge';
 import { Port } from '@/types/api';
 import { getPortTypeInfo } from '@/lib/portTypes';
+import { KillImpactSummary } from './KillImpactSummary';
 import {
   AlertDialog,
   AlertDialogAction,
@@ -17,31 +18,37 @@ import {
 
 interface PortListItemProps {
   port: Port;
-  onKill: (pid: number) => void;
+  onKill: (pid: number, port: number) => void;
   isPinned?: boolean;
 }
 
 export function PortListItem({ port, onKill, isPinned = false }: PortListItemProps) {
   const isOccupied = port.status === 'occupied';
   const portType = getPortTypeInfo(port.port);
+  const alternatives = port.alternatives?.length
+    ? ` Try ${port.alternatives.join(', ')} instead.`
+    : '';
   const PortIcon = portType.icon;
 
   const statusColors = {
     free: 'border-emerald-500/40 bg-emerald-500/10',
     occupied: 'border-amber-500/40 bg-amber-500/10',
     system: 'border-blue-500/40 bg-blue-500/10',
+    unknown: 'border-slate-500/40 bg-slate-500/10',
   };
 
   const dotColors = {
     free: 'bg-emerald-500',
     occupied: 'bg-amber-500',
     system: 'bg-blue-500',
+    unknown: 'bg-slate-500',
   };
 
   const badgeVariants = {
     free: 'default',
     occupied: 'destructive',
     system: 'secondary',
+    unknown: 'outline',
   } as const;
 
   return (
@@ -75,6 +82,26 @@ export function PortListItem({ port, onKill, isPinned = false }: PortListItemPro
           {port.status}
         </Badge>
 
+        {/* Ephemeral Range Warning */}
+        {port.ephemeral_conflict ? (
+          <Badge
+            variant="destructive"
+            className="text-[10px] px-1.5 py-0 h-5 flex-shrink-0"
+            title={`An outgoing connection is using this port, so a service can't bind it.${alternatives}`}
+          >
+            <AlertTriangle className="h-3 w-3 mr-1" />
+            client conflict
+          </Badge>
+        ) : port.in_ephemeral_range && (
+          <Badge
+            variant="outline"
+            className="text-[10px] px-1.5 py-0 h-5 flex-shrink-0 border-amber-500/60 text-amber-600"
+            title={`The kernel may hand this port to outgoing connections.${alternatives}`}
+          >
+            ephemeral
+          </Badge>
+        )}
+
         {/* Process Info */}
         <div className="flex-1 min-w-0">
           {isOccupied && port.process ? (
@@ -85,7 +112,11 @@ export function PortListItem({ port, onKill, isPinned = false }: PortListItemPro
               <div className="text-[10px] text-muted-foreground flex-shrink-0">PID: {port.process.pid}</div>
             </div>
           ) : (
-            <div className="text-xs text-muted-foreground">No process</div>
+            <div className="text-xs text-muted-foreground">
+              {port.status === 'unknown'
+                ? `Owner hidden (${port.unattributed?.replace(/_/g, ' ') ?? 'unknown'})`
+                : 'No process'}
+            </div>
           )}
         </div>
       </div>
@@ -111,11 +142,12 @@ export function PortListItem({ port, onKill, isPinned = false }: PortListItemPro
                 <br />
                 This action cannot be undone and may cause data loss.
               </AlertDialogDescription>
+              <KillImpactSummary pid={port.process.pid} />
             </AlertDialogHeader>
             <AlertDialogFooter>
               <AlertDialogCancel>Cancel</AlertDialogCancel>
               <AlertDialogAction
-                onClick={() => onKill(port.process!.pid)}
+                onClick={() => onKill(port.process!.pid, port.port)}
                 className="bg-destructive text-destructive-foreground hover:bg-destructive/90"
               >
                 Kill Process
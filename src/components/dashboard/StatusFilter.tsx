@@ -17,6 +17,7 @@ export function StatusFilter({ selectedStatuses, onStatusToggle }: StatusFilterP
     { value: 'free', label: 'Free' },
     { value: 'occupied', label: 'Occupied' },
     { value: 'system', label: 'System' },
+    { value: 'unknown', label: 'Unknown' },
   ];
 
   return (
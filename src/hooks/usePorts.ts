@@ -1,6 +1,6 @@
 import { useQuery, useQueryClient } from '@tanstack/react-query';
 import { useState, useEffect } from 'react';
-import { getCommonPorts, getActivePorts } from '@/lib/tauri';
+import { getCommonPorts, getActivePorts, getPortsByInterface } from '@/lib/tauri';
 
 export function usePinnedPorts(refreshInterval: number = 2000) {
   const [pinnedPorts, setPinnedPorts] = useState<number[] | undefined>(undefined);
@@ -47,7 +47,27 @@ export function usePinnedPorts(refreshInterval: number = 2000) {
 export function useAllPorts(refreshInterval: number = 3000) {
   return useQuery({
     queryKey: ['ports', 'all'],
-    queryFn: getActivePorts,
+    queryFn: () => getActivePorts(),
+    refetchInterval: refreshInterval,
+    refetchIntervalInBackground: true,
+  });
+}
+
+// Status of specific ports, including free ones with their ephemeral range checks
+export function useScannedPorts(ports: number[], refreshInterval: number = 3000) {
+  return useQuery({
+    queryKey: ['ports', 'scanned', ports],
+    queryFn: () => getCommonPorts(ports),
+    enabled: ports.length > 0,
+    refetchInterval: refreshInterval,
+    refetchIntervalInBackground: true,
+  });
+}
+
+export function usePortsByInterface(refreshInterval: number = 3000) {
+  return useQuery({
+    queryKey: ['ports', 'interfaces'],
+    queryFn: () => getPortsByInterface(),
     refetchInterval: refreshInterval,
     refetchIntervalInBackground: true,
   });
@@ -1,9 +1,62 @@
 import { invoke } from '@tauri-apps/api/core';
-import { Port, SystemInfo } from '@/types/api';
+import {
+  AuditEntry,
+  BacklogReport,
+  CommandError,
+  ConnectionDetails,
+  Diagnostics,
+  ExposureReport,
+  GraphFormat,
+  InterfacePorts,
+  KillImpact,
+  LaunchSpec,
+  OtherSocket,
+  PauseMethod,
+  PausedProcess,
+  Port,
+  PortDiagnosis,
+  PortRange,
+  PortReservations,
+  ProcessAncestry,
+  ProcessSockets,
+  ReportFormat,
+  RestartOptions,
+  RestartResult,
+  ServiceGraph,
+  SocketLeakReport,
+  SystemInfo,
+  UnixSocket,
+  UserPorts,
+} from '@/types/api';
+
+export function isCommandError(error: unknown): error is CommandError {
+  return typeof error === 'object' && error !== null && 'code' in error && 'message' in error;
+}
+
+export function errorMessage(error: unknown): string {
+  if (isCommandError(error)) return error.message;
+  return error instanceof Error ? error.message : String(error);
+}
 
 // Port monitoring commands
-export async function getActivePorts(): Promise<Port[]> {
-  return await invoke<Port[]>('get_active_ports');
+export async function getActivePorts(user?: string): Promise<Port[]> {
+  return await invoke<Port[]>('get_active_ports', { user });
+}
+
+export async function getPortsGroupedByUser(): Promise<UserPorts[]> {
+  return await invoke<UserPorts[]>('get_ports_grouped_by_user');
+}
+
+export async function setNamespaceScanning(enabled: boolean): Promise<void> {
+  return await invoke<void>('set_namespace_scanning', { enabled });
+}
+
+export async function setServicePorts(ports: number[]): Promise<void> {
+  return await invoke<void>('set_service_ports', { ports });
+}
+
+export async function getPortsByInterface(): Promise<InterfacePorts[]> {
+  return await invoke<InterfacePorts[]>('get_ports_by_interface');
 }
 
 export async function getCommonPorts(ports?: number[]): Promise<Port[]> {
@@ -14,7 +67,59 @@ export async function getPortDetails(port: number): Promise<Port | null> {
   return await invoke<Port | null>('get_port_details', { port });
 }
 
+export async function diagnosePort(port: number): Promise<PortDiagnosis> {
+  return await invoke<PortDiagnosis>('diagnose_port', { port });
+}
+
+export async function getConnectionDetails(port: number): Promise<ConnectionDetails[]> {
+  return await invoke<ConnectionDetails[]>('get_connection_details', { port });
+}
+
+export async function getBacklogReport(): Promise<BacklogReport> {
+  return await invoke<BacklogReport>('get_backlog_report');
+}
+
+export async function getSocketLeakReport(): Promise<SocketLeakReport> {
+  return await invoke<SocketLeakReport>('get_socket_leak_report');
+}
+
+export async function getExposureReport(): Promise<ExposureReport> {
+  return await invoke<ExposureReport>('get_exposure_report');
+}
+
+export async function exportExposureReport(format: ReportFormat): Promise<string> {
+  return await invoke<string>('export_exposure_report', { format });
+}
+
+export async function getServiceGraph(): Promise<ServiceGraph> {
+  return await invoke<ServiceGraph>('get_service_graph');
+}
+
+export async function exportServiceGraph(format: GraphFormat): Promise<string> {
+  return await invoke<string>('export_service_graph', { format });
+}
+
+export async function getUnixSockets(all?: boolean): Promise<UnixSocket[]> {
+  return await invoke<UnixSocket[]>('get_unix_sockets', { all });
+}
+
+export async function getOtherSockets(): Promise<OtherSocket[]> {
+  return await invoke<OtherSocket[]>('get_other_sockets');
+}
+
 // Process management commands
+export async function getProcessSockets(pid: number, includeDescendants?: boolean): Promise<ProcessSockets> {
+  return await invoke<ProcessSockets>('get_process_sockets', { pid, includeDescendants });
+}
+
+export async function getProcessAncestry(pid: number): Promise<ProcessAncestry> {
+  return await invoke<ProcessAncestry>('get_process_ancestry', { pid });
+}
+
+export async function getKillImpact(pid: number): Promise<KillImpact> {
+  return await invoke<KillImpact>('get_kill_impact', { pid });
+}
+
 export async function killProcess(pid: number): Promise<boolean> {
   return await invoke<boolean>('kill_process', { pid });
 }
@@ -23,11 +128,75 @@ export async function killProcessByPort(port: number): Promise<boolean> {
   return await invoke<boolean>('kill_process_by_port', { port });
 }
 
+export async function killProcessBySocket(path: string): Promise<boolean> {
+  return await invoke<boolean>('kill_process_by_socket', { path });
+}
+
+export async function killProcessPrivileged(pid: number, port: number): Promise<boolean> {
+  return await invoke<boolean>('kill_process_privileged', { pid, port });
+}
+
+export async function stopSystemdUnit(pid: number): Promise<boolean> {
+  return await invoke<boolean>('stop_systemd_unit', { pid });
+}
+
+export async function restartSystemdUnit(pid: number): Promise<boolean> {
+  return await invoke<boolean>('restart_systemd_unit', { pid });
+}
+
+export async function stopContainer(id: string): Promise<boolean> {
+  return await invoke<boolean>('stop_container', { id });
+}
+
+export async function getAuditLog(limit?: number): Promise<AuditEntry[]> {
+  return await invoke<AuditEntry[]>('get_audit_log', { limit });
+}
+
+export async function getLaunchSpec(port: number): Promise<LaunchSpec> {
+  return await invoke<LaunchSpec>('get_launch_spec', { port });
+}
+
+export async function restartPortOwner(port: number, options?: RestartOptions): Promise<RestartResult> {
+  return await invoke<RestartResult>('restart_port_owner', { port, options });
+}
+
+export async function pauseProcess(pid: number, method?: PauseMethod): Promise<PausedProcess> {
+  return await invoke<PausedProcess>('pause_process', { pid, method });
+}
+
+export async function resumeProcess(pid: number): Promise<boolean> {
+  return await invoke<boolean>('resume_process', { pid });
+}
+
+export async function getPausedProcesses(): Promise<PausedProcess[]> {
+  return await invoke<PausedProcess[]>('get_paused_processes');
+}
+
 // System commands
 export async function getSystemInfo(): Promise<SystemInfo> {
   return await invoke<SystemInfo>('get_system_info');
 }
 
+export async function getPortReservations(): Promise<PortReservations> {
+  return await invoke<PortReservations>('get_port_reservations');
+}
+
+export async function reservePorts(ports: PortRange[]): Promise<PortReservations> {
+  return await invoke<PortReservations>('reserve_ports', { ports });
+}
+
+export async function releasePorts(ports: PortRange[]): Promise<PortReservations> {
+  return await invoke<PortReservations>('release_ports', { ports });
+}
+
 export async function isElevated(): Promise<boolean> {
   return await invoke<boolean>('is_elevated');
 }
+
+export async function hasPrivilegedHelper(): Promise<boolean> {
+  return await invoke<boolean>('has_privileged_helper');
+}
+
+export async function getDiagnostics(): Promise<Diagnostics> {
+  return await invoke<Diagnostics>('get_diagnostics');
+}
@@ -1,5 +1,11 @@
-export type PortStatus = 'free' | 'occupied' | 'system';
-export type Protocol = 'TCP' | 'UDP';
+export type PortStatus = 'free' | 'occupied' | 'system' | 'unknown';
+export type AttributionFailure =
+  | 'no_owner'
+  | 'permission_denied'
+  | 'hidepid'
+  | 'other_namespace'
+  | 'unknown';
+export type Protocol = 'TCP' | 'UDP' | 'RAW' | 'ICMP' | 'PACKET' | 'SCTP';
 
 export interface Port {
   port: number;
@@ -8,6 +14,28 @@ export interface Port {
   process?: Process;
   ip_address: string;
   created_at?: string;
+  unattributed?: AttributionFailure;
+  container?: ContainerInfo;
+  namespace?: NetNamespace;
+  in_ephemeral_range: boolean;
+  ephemeral_conflict: boolean;
+  alternatives?: number[];
+}
+
+export interface NetNamespace {
+  inode: number;
+  name?: string;
+  host: boolean;
+}
+
+export interface ContainerInfo {
+  runtime: 'docker' | 'podman';
+  id: string;
+  name: string;
+  image: string;
+  compose_project?: string;
+  compose_service?: string;
+  container_port: number;
 }
 
 export interface Process {
@@ -15,11 +43,326 @@ export interface Process {
   name: string;
   path: string;
   command: string;
+  parent_pid?: number;
   working_dir?: string;
   cpu_usage: number;
   memory_usage: number;
   started_at: string;
   user?: string;
+  uid?: number;
+  group?: string;
+  gid?: number;
+  systemd_unit?: SystemdUnit;
+  namespace_pid?: number;
+}
+
+export interface SystemdUnit {
+  name: string;
+  scope: 'system' | 'user';
+  uid?: number;
+  managed: boolean;
+}
+
+export interface UnixSocket {
+  path?: string;
+  socket_type: 'stream' | 'dgram' | 'seqpacket';
+  state: string;
+  listening: boolean;
+  inode: number;
+  process?: Process;
+  stale: boolean;
+}
+
+export interface OtherSocket {
+  protocol: Protocol;
+  local_address: string;
+  port?: number;
+  ip_protocol?: number;
+  ethertype?: number;
+  interface?: string;
+  inode: number;
+  process?: Process;
+}
+
+export type TcpTimer = 'off' | 'retransmit' | 'keep_alive' | 'time_wait' | 'zero_window_probe';
+
+export interface TcpInfo {
+  rtt_us: number;
+  rtt_var_us: number;
+  rto_us: number;
+  snd_cwnd: number;
+  snd_mss: number;
+  unacked: number;
+  lost: number;
+  total_retrans: number;
+  last_data_recv_ms: number;
+  last_data_sent_ms: number;
+  bytes_acked: number;
+  bytes_received: number;
+}
+
+export interface TcpDetails {
+  tx_queue: number;
+  rx_queue: number;
+  max_backlog?: number;
+  timer: TcpTimer;
+  timer_expires_ms: number;
+  retransmits: number;
+  uid: number;
+  inode: number;
+  info?: TcpInfo;
+}
+
+export type BindOutcome =
+  | 'bound'
+  | 'in_use'
+  | 'permission_denied'
+  | 'address_unavailable'
+  | 'failed';
+
+export interface BindAttempt {
+  address: string;
+  reuse_address: boolean;
+  outcome: BindOutcome;
+  error?: string;
+}
+
+export type DiagnosisCause =
+  | 'listener'
+  | 'namespace_listener'
+  | 'time_wait'
+  | 'close_wait'
+  | 'ephemeral_client'
+  | 'privileged'
+  | 'ephemeral_range'
+  | 'reserved';
+
+export interface DiagnosisFinding {
+  cause: DiagnosisCause;
+  blocking: boolean;
+  summary: string;
+  sockets?: number;
+  pid?: number;
+  process_name?: string;
+  namespace?: NetNamespace;
+  fixes: string[];
+}
+
+export interface PortDiagnosis {
+  port: number;
+  bindable: boolean;
+  summary: string;
+  findings: DiagnosisFinding[];
+  bind_attempts: BindAttempt[];
+  alternatives: number[];
+}
+
+export interface ConnectionDetails {
+  local_address: string;
+  local_port: number;
+  remote_address: string;
+  remote_port: number;
+  protocol: Protocol;
+  state: string;
+  pid: number;
+  tcp?: TcpDetails;
+}
+
+export interface ListenerBacklog {
+  port: number;
+  local_address: string;
+  process?: Process;
+  queued: number;
+  max_backlog?: number;
+  saturation?: number;
+  filling: boolean;
+}
+
+export interface ListenCounters {
+  timestamp: string;
+  listen_overflows: number;
+  listen_drops: number;
+}
+
+export interface BacklogReport {
+  listeners: ListenerBacklog[];
+  counters: ListenCounters[];
+  recent_overflows?: number;
+}
+
+export interface SocketUsageSample {
+  timestamp: string;
+  close_wait: number;
+  open_fds?: number;
+}
+
+export type LeakKind = 'close_wait' | 'fd_exhaustion';
+
+export interface LeakWarning {
+  kind: LeakKind;
+  message: string;
+}
+
+export interface ProcessSocketUsage {
+  process: Process;
+  sockets_by_state: Record<string, number>;
+  close_wait: number;
+  open_fds?: number;
+  fd_limit?: number;
+  fd_usage?: number;
+  history: SocketUsageSample[];
+  warnings: LeakWarning[];
+}
+
+export interface SocketLeakReport {
+  generated_at: string;
+  processes: ProcessSocketUsage[];
+}
+
+export interface InterfaceAddress {
+  interface: string;
+  address: string;
+  prefix_len: number;
+  loopback: boolean;
+  up: boolean;
+}
+
+export type InterfaceKind =
+  | 'loopback'
+  | 'physical'
+  | 'wireless'
+  | 'container_bridge'
+  | 'bridge'
+  | 'virtual';
+
+export interface NetworkInterface {
+  name: string;
+  kind: InterfaceKind;
+  up: boolean;
+  addresses: InterfaceAddress[];
+}
+
+export interface InterfacePorts {
+  interface?: NetworkInterface;
+  ports: Port[];
+}
+
+export type Exposure = 'loopback_only' | 'lan' | 'specific_interface' | 'all_interfaces';
+
+export interface ExposureEntry {
+  port: number;
+  protocol: Protocol;
+  local_address: string;
+  exposure: Exposure;
+  interface?: string;
+  service?: string;
+  pid?: number;
+  process_name?: string;
+  flagged: boolean;
+}
+
+export interface ExposureReport {
+  generated_at: string;
+  hostname: string;
+  interfaces: InterfaceAddress[];
+  listeners: ExposureEntry[];
+}
+
+export type ReportFormat = 'json' | 'markdown';
+
+export interface ProcessSockets {
+  process: Process;
+  listeners: ConnectionDetails[];
+  connections: ConnectionDetails[];
+  unix_sockets: UnixSocket[];
+  descendants: ProcessSockets[];
+}
+
+export type LauncherKind =
+  | 'terminal'
+  | 'ide'
+  | 'shell'
+  | 'multiplexer'
+  | 'supervisor'
+  | 'container'
+  | 'scheduler'
+  | 'remote_login'
+  | 'init';
+
+export interface Launcher {
+  kind: LauncherKind;
+  name: string;
+}
+
+export interface AncestorProcess {
+  pid: number;
+  name: string;
+  command: string;
+  working_dir?: string;
+  launcher?: Launcher;
+}
+
+export interface ProcessAncestry {
+  process: Process;
+  ancestors: AncestorProcess[];
+  launched_by?: Launcher;
+}
+
+export interface ImpactedClient {
+  port: number;
+  remote_address: string;
+  remote_port: number;
+  local: boolean;
+  pid?: number;
+  process_name?: string;
+}
+
+export interface ChildProcess {
+  pid: number;
+  parent_pid: number;
+  name: string;
+  command: string;
+}
+
+export type RestartManager =
+  | { kind: 'systemd'; unit: SystemdUnit }
+  | { kind: 'container'; runtime: string; id: string; name?: string };
+
+export interface KillImpact {
+  process: Process;
+  listening_ports: number[];
+  clients: ImpactedClient[];
+  children: ChildProcess[];
+  restarted_by: RestartManager[];
+  summary: string;
+}
+
+export interface ServiceNode {
+  id: string;
+  pid?: number;
+  name: string;
+  listening_ports: number[];
+}
+
+export interface ServiceEdge {
+  from: string;
+  to: string;
+  port: number;
+  connections: number;
+}
+
+export interface ServiceGraph {
+  generated_at: string;
+  nodes: ServiceNode[];
+  edges: ServiceEdge[];
+}
+
+export type GraphFormat = 'json' | 'dot' | 'mermaid';
+
+export interface UserPorts {
+  user?: string;
+  uid?: number;
+  ports: Port[];
 }
 
 export interface SystemInfo {
@@ -28,4 +371,139 @@ export interface SystemInfo {
   hostname: string;
   cpu_count: number;
   total_memory: number;
+  uptime_secs: number;
+  interfaces: NetworkInterface[];
+  network: NetworkLimits;
+}
+
+export interface PortRange {
+  start: number;
+  end: number;
+}
+
+export interface SocketStats {
+  sockets_used: number;
+  tcp_in_use: number;
+  tcp_orphaned: number;
+  tcp_time_wait: number;
+  tcp_allocated: number;
+  udp_in_use: number;
+  raw_in_use: number;
+  tcp6_in_use: number;
+  udp6_in_use: number;
+}
+
+export interface PortReservations {
+  reserved: PortRange[];
+  ephemeral_port_range?: PortRange;
+  writable: boolean;
+}
+
+export interface NetworkLimits {
+  ephemeral_port_range?: PortRange;
+  reserved_ports: PortRange[];
+  somaxconn?: number;
+  tcp_tw_reuse?: number;
+  tcp_fin_timeout?: number;
+  tcp_max_tw_buckets?: number;
+  file_max?: number;
+  files_open?: number;
+  fd_limit_soft?: number;
+  fd_limit_hard?: number;
+  sockets?: SocketStats;
+}
+
+export interface LaunchSpec {
+  pid: number;
+  program: string;
+  args: string[];
+  working_dir?: string;
+  env: Record<string, string>;
+  uid?: number;
+  gid?: number;
+}
+
+export interface RestartOptions {
+  command?: string[];
+  working_dir?: string;
+  env?: Record<string, string>;
+  unset_env?: string[];
+  timeout_ms?: number;
+}
+
+export interface RestartResult {
+  port: number;
+  old_pid: number;
+  new_pid: number;
+  spec: LaunchSpec;
+}
+
+export type PauseMethod = 'signal' | 'cgroup_freeze';
+
+export interface PausedProcess {
+  pid: number;
+  name: string;
+  method: PauseMethod;
+  paused_at: string;
+  started_at: string;
+  cgroup?: string;
+  listening_ports: number[];
+  warning?: string;
+}
+
+export interface Diagnostics {
+  os: string;
+  backend: string;
+  elevated: boolean;
+  capabilities: { name: string; effective: boolean }[];
+  hidepid?: string;
+  isolated_pid_namespace: boolean;
+  unreadable_processes: number;
+  total_sockets: number;
+  unattributed: { reason: AttributionFailure; count: number }[];
+  notes: string[];
+}
+
+export type ErrorCode =
+  | 'permission_denied'
+  | 'process_not_found'
+  | 'port_not_in_use'
+  | 'socket_not_in_use'
+  | 'protected'
+  | 'timeout'
+  | 'backend_unavailable'
+  | 'unsupported'
+  | 'authentication_cancelled'
+  | 'elevation_required'
+  | 'privilege_required'
+  | 'relaunch_failed'
+  | 'invalid_input'
+  | 'internal';
+
+// Every command rejects with this shape; extra fields carry context such as `pid` or `port`
+export interface CommandError {
+  code: ErrorCode;
+  message: string;
+  [context: string]: unknown;
+}
+
+export interface AuditTarget {
+  pid: number;
+  name: string;
+  command: string;
+  started_at: string;
+  uid?: number;
+  user?: string;
+}
+
+export interface AuditEntry {
+  timestamp: string;
+  user: string;
+  action: string;
+  pid?: number;
+  target?: AuditTarget;
+  port?: number;
+  signals: string[];
+  success: boolean;
+  error?: string;
 }